@@ -0,0 +1,143 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! This module provides a helper for representing collections as a list of
+//! values within a single XML attribute.
+
+use quick_xml::events::BytesStart;
+
+use crate::{Element, Error, XmlDeserializeAttr, XmlSerializeAttr};
+
+/// The separator between list entries in an XML Schema `xs:list` type.
+const DEFAULT_SEPARATOR: &str = " ";
+
+/// The name of the attribute used to capture the text of each entry.
+const SCRATCH_ATTRIBUTE_NAME: &str = "_";
+
+/// A view of a collection which serializes its entries as a single XML
+/// attribute value, separated by a common separator.
+///
+/// For example, serializing `AttrList::new(&flags)` as an attribute named
+/// "Flags" produces output like `Flags="Read Flagged"`. Entries are
+/// represented as they would be as an attribute value of their own. Entries
+/// which would produce no attribute, such as `None`, are omitted, and an error
+/// serializing any entry is returned as-is.
+///
+/// Because the separator isn't part of the collection's type, deserialization
+/// is provided via associated functions which take the separator as an
+/// argument rather than via an implementation of `XmlDeserializeAttr`.
+#[derive(Debug)]
+pub struct AttrList<'a, T> {
+    items: &'a [T],
+    separator: &'a str,
+}
+
+impl<'a, T> AttrList<'a, T> {
+    /// Creates a view of a collection with entries separated by a single
+    /// space, as in an XML Schema `xs:list`.
+    pub fn new(items: &'a [T]) -> Self {
+        Self::with_separator(items, DEFAULT_SEPARATOR)
+    }
+
+    /// Creates a view of a collection with entries separated by `separator`.
+    pub fn with_separator(items: &'a [T], separator: &'a str) -> Self {
+        Self { items, separator }
+    }
+}
+
+impl<T> AttrList<'_, T>
+where
+    T: XmlDeserializeAttr,
+{
+    /// Deserializes a collection from the named attribute of an XML element,
+    /// with entries separated by `separator`.
+    pub fn deserialize_from_attribute<C>(
+        element: &Element,
+        name: &str,
+        separator: &str,
+    ) -> Result<C, Error>
+    where
+        C: FromIterator<T>,
+    {
+        match element.attribute(name) {
+            Some(value) => Self::deserialize_from_attribute_value(value, separator),
+            None => Err(Error::UnexpectedStructure(format!(
+                "missing attribute `{name}` on `{}`",
+                element.name()
+            ))),
+        }
+    }
+
+    /// Deserializes a collection from the unescaped value of an XML attribute,
+    /// with entries separated by `separator`.
+    ///
+    /// If the separator consists only of whitespace, entries are separated by
+    /// any run of whitespace and leading or trailing whitespace is ignored.
+    pub fn deserialize_from_attribute_value<C>(value: &str, separator: &str) -> Result<C, Error>
+    where
+        C: FromIterator<T>,
+    {
+        if separator.trim().is_empty() {
+            value
+                .split_whitespace()
+                .map(T::deserialize_from_attribute_value)
+                .collect()
+        } else if value.is_empty() {
+            Ok(std::iter::empty().collect())
+        } else {
+            value
+                .split(separator)
+                .map(T::deserialize_from_attribute_value)
+                .collect()
+        }
+    }
+}
+
+impl<T> Clone for AttrList<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AttrList<'_, T> {}
+
+/// Serializes the entries of a collection as a single attribute value.
+impl<T> XmlSerializeAttr for AttrList<'_, T>
+where
+    T: XmlSerializeAttr,
+{
+    fn serialize_as_attribute(&self, start_tag: &mut BytesStart, name: &str) -> Result<(), Error> {
+        let mut entries = Vec::new();
+        for item in self.items {
+            if let Some(text) = attribute_text(item)? {
+                entries.push(text);
+            }
+        }
+
+        start_tag.push_attribute((name, entries.join(self.separator).as_str()));
+
+        Ok(())
+    }
+}
+
+/// Gets the unescaped text of a value as it would be serialized as an
+/// attribute value, if it would be serialized at all.
+fn attribute_text<T>(value: &T) -> Result<Option<String>, Error>
+where
+    T: XmlSerializeAttr,
+{
+    let mut scratch = BytesStart::new(SCRATCH_ATTRIBUTE_NAME);
+    value.serialize_as_attribute(&mut scratch, SCRATCH_ATTRIBUTE_NAME)?;
+
+    let attribute = match scratch.attributes().next() {
+        Some(attribute) => attribute.map_err(quick_xml::Error::from)?,
+        None => return Ok(None),
+    };
+
+    // The value was escaped when added to the start tag, and will be escaped
+    // again when the list is added, so we need to undo the escaping here.
+    let text = attribute.unescape_value()?;
+
+    Ok(Some(text.into_owned()))
+}
@@ -0,0 +1,79 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! This module provides a wrapper for serializing text as CDATA sections.
+
+use quick_xml::events::{BytesCData, Event};
+
+use crate::{Content, Error, XmlDeserialize, XmlSerialize, XmlWriter};
+
+/// The sequence which terminates a CDATA section.
+const CDATA_END: &str = "]]>";
+
+/// A wrapper for text which is serialized as one or more CDATA sections rather
+/// than as an escaped text node.
+///
+/// Text containing the sequence `]]>` cannot be represented in a single CDATA
+/// section, so it is split between sections immediately following `]]`.
+///
+/// When deserializing, CDATA sections and text nodes are treated identically,
+/// so the wrapped value is deserialized as it would be without the wrapper.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cdata<T>(pub T);
+
+impl<T> Cdata<T> {
+    /// Consumes the wrapper, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Cdata<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// Serializes text as CDATA sections.
+impl<T> XmlSerialize for Cdata<T>
+where
+    T: AsRef<str>,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        write_cdata_sections(writer, self.0.as_ref())
+    }
+}
+
+impl<T> XmlDeserialize for Cdata<T>
+where
+    T: XmlDeserialize,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        T::deserialize_child_nodes(content).map(Self)
+    }
+}
+
+/// Writes text as one or more CDATA sections, splitting the text wherever it
+/// contains the sequence terminating a CDATA section.
+fn write_cdata_sections<W>(writer: &mut XmlWriter<W>, text: &str) -> Result<(), Error>
+where
+    W: std::io::Write,
+{
+    let mut remaining = text;
+    while let Some(idx) = remaining.find(CDATA_END) {
+        // End this section between the `]]` and `>` so that neither section
+        // contains the terminating sequence.
+        let (section, rest) = remaining.split_at(idx + 2);
+        writer.write_event(Event::CData(BytesCData::new(section)))?;
+
+        remaining = rest;
+    }
+
+    writer.write_event(Event::CData(BytesCData::new(remaining)))?;
+
+    Ok(())
+}
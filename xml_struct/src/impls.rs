@@ -2,19 +2,27 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-//! This module provides implementations of serialization for common types from
-//! the standard library.
+//! This module provides implementations of serialization and deserialization
+//! for common types from the standard library.
 
-use quick_xml::{
-    events::{BytesText, Event},
-    Writer,
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    hash::{BuildHasher, Hash},
+    rc::Rc,
+    sync::Arc,
 };
 
-use crate::{Error, XmlSerialize, XmlSerializeAttr};
+use quick_xml::events::{BytesText, Event};
+
+use crate::{
+    Content, Element, Error, XmlDeserialize, XmlDeserializeAttr, XmlSerialize, XmlSerializeAttr,
+    XmlWriter,
+};
 
 /// Serializes a string as a text content node.
 impl XmlSerialize for str {
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
     where
         W: std::io::Write,
     {
@@ -24,24 +32,9 @@ impl XmlSerialize for str {
     }
 }
 
-/// Serializes a reference to a string as a text content node.
-impl<T> XmlSerialize for &T
-where
-    T: AsRef<str>,
-{
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
-    where
-        W: std::io::Write,
-    {
-        writer.write_event(Event::Text(BytesText::new(self.as_ref())))?;
-
-        Ok(())
-    }
-}
-
 /// Serializes a string as a text content node.
 impl XmlSerialize for String {
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
     where
         W: std::io::Write,
     {
@@ -51,24 +44,12 @@ impl XmlSerialize for String {
     }
 }
 
-/// Serializes a string as a text content node.
-impl XmlSerialize for &str {
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
-    where
-        W: std::io::Write,
-    {
-        writer.write_event(Event::Text(BytesText::new(self)))?;
-
-        Ok(())
-    }
-}
-
 /// Serializes a boolean as a text content node.
 ///
 /// `true` is serialized as the string value "true", while `false` is serialized
 /// as the string value "false".
 impl XmlSerialize for bool {
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
     where
         W: std::io::Write,
     {
@@ -87,7 +68,7 @@ impl<T> XmlSerialize for Option<T>
 where
     T: XmlSerialize,
 {
-    fn serialize_as_element<W>(&self, writer: &mut Writer<W>, name: &str) -> Result<(), Error>
+    fn serialize_as_element<W>(&self, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
     where
         W: std::io::Write,
     {
@@ -97,7 +78,7 @@ where
         }
     }
 
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
     where
         W: std::io::Write,
     {
@@ -106,6 +87,24 @@ where
             None => Ok(()),
         }
     }
+
+    fn serialize_attributes<W>(
+        &self,
+        writer: &XmlWriter<W>,
+        start_tag: &mut quick_xml::events::BytesStart,
+    ) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        match self {
+            Some(value) => <T as XmlSerialize>::serialize_attributes(value, writer, start_tag),
+            None => Ok(()),
+        }
+    }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
+    }
 }
 
 /// Serializes the contents of a `Vec<T>` as content nodes.
@@ -116,7 +115,7 @@ impl<T> XmlSerialize for Vec<T>
 where
     T: XmlSerialize,
 {
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
     where
         W: std::io::Write,
     {
@@ -130,36 +129,388 @@ where
 
         Ok(())
     }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
+    }
 }
 
-/// Serializes a string as an XML attribute value.
-impl XmlSerializeAttr for str {
-    fn serialize_as_attribute(&self, start_tag: &mut quick_xml::events::BytesStart, name: &str) {
-        start_tag.push_attribute((name, self));
+/// Serializes the contents of a slice as content nodes.
+///
+/// Each element of the slice is serialized via its `serialize_child_nodes()`
+/// implementation, in order.
+impl<T> XmlSerialize for [T]
+where
+    T: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        serialize_each(self, writer)
+    }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
     }
 }
 
-/// Serializes a reference to a string as an XML attribute value.
-impl<T> XmlSerializeAttr for &T
+/// Serializes the contents of an array as content nodes.
+///
+/// Each element of the array is serialized via its `serialize_child_nodes()`
+/// implementation, in order.
+impl<T, const N: usize> XmlSerialize for [T; N]
 where
-    T: AsRef<str>,
+    T: XmlSerialize,
 {
-    fn serialize_as_attribute(&self, start_tag: &mut quick_xml::events::BytesStart, name: &str) {
-        start_tag.push_attribute((name, self.as_ref()));
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        serialize_each(self, writer)
+    }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
+    }
+}
+
+/// Serializes the contents of a `VecDeque<T>` as content nodes.
+///
+/// Each element is serialized via its `serialize_child_nodes()`
+/// implementation, from front to back.
+impl<T> XmlSerialize for VecDeque<T>
+where
+    T: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        serialize_each(self, writer)
+    }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
+    }
+}
+
+/// Serializes the contents of a `BTreeSet<T>` as content nodes.
+///
+/// Each element is serialized via its `serialize_child_nodes()`
+/// implementation, in ascending order.
+impl<T> XmlSerialize for BTreeSet<T>
+where
+    T: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        serialize_each(self, writer)
+    }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
+    }
+}
+
+/// Serializes the contents of a `HashSet<T>` as content nodes.
+///
+/// Each element is serialized via its `serialize_child_nodes()`
+/// implementation in the set's iteration order, which is unspecified and may
+/// differ between runs. Consumers which require stable output should use a
+/// `BTreeSet<T>` or another ordered collection instead.
+impl<T, S> XmlSerialize for HashSet<T, S>
+where
+    T: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        serialize_each(self, writer)
+    }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
+    }
+}
+
+/// Serializes each of a sequence of values as content nodes, in order.
+fn serialize_each<'a, T, W>(
+    values: impl IntoIterator<Item = &'a T>,
+    writer: &mut XmlWriter<W>,
+) -> Result<(), Error>
+where
+    T: XmlSerialize + 'a,
+    W: std::io::Write,
+{
+    for value in values {
+        <T as XmlSerialize>::serialize_child_nodes(value, writer)?;
+    }
+
+    Ok(())
+}
+
+/// Serializes the entries of a `BTreeMap<K, V>` as content nodes.
+///
+/// Each value is serialized as an element named by its key, in ascending key
+/// order.
+impl<K, V> XmlSerialize for BTreeMap<K, V>
+where
+    K: AsRef<str>,
+    V: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        serialize_keyed_elements(self, writer)
+    }
+}
+
+/// Serializes the entries of a `HashMap<K, V>` as content nodes.
+///
+/// Each value is serialized as an element named by its key, in the map's
+/// iteration order, which is unspecified and may differ between runs.
+/// Consumers which require stable output should use a `BTreeMap<K, V>`
+/// instead.
+impl<K, V, S> XmlSerialize for HashMap<K, V, S>
+where
+    K: AsRef<str>,
+    V: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        serialize_keyed_elements(self, writer)
+    }
+}
+
+/// Serializes each of a sequence of key/value pairs as an element named by the
+/// key.
+fn serialize_keyed_elements<'a, K, V, W>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    writer: &mut XmlWriter<W>,
+) -> Result<(), Error>
+where
+    K: AsRef<str> + 'a,
+    V: XmlSerialize + 'a,
+    W: std::io::Write,
+{
+    for (key, value) in entries {
+        <V as XmlSerialize>::serialize_as_element(value, writer, key.as_ref())?;
+    }
+
+    Ok(())
+}
+
+/// Implements serialization and deserialization of tuples as consecutive
+/// content nodes, one member after another.
+macro_rules! impl_for_tuple {
+    ($( ( $( $name:ident . $idx:tt ),+ ) )*) => {
+        $(
+        /// Serializes the members of a tuple as consecutive content nodes.
+        impl<$( $name ),+> XmlSerialize for ($( $name, )+)
+        where
+            $( $name: XmlSerialize, )+
+        {
+            fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+            where
+                W: std::io::Write,
+            {
+                $( <$name as XmlSerialize>::serialize_child_nodes(&self.$idx, writer)?; )+
+
+                Ok(())
+            }
+
+            fn serialize_attributes<W>(
+                &self,
+                writer: &XmlWriter<W>,
+                start_tag: &mut quick_xml::events::BytesStart,
+            ) -> Result<(), Error>
+            where
+                W: std::io::Write,
+            {
+                $( <$name as XmlSerialize>::serialize_attributes(&self.$idx, writer, start_tag)?; )+
+
+                Ok(())
+            }
+
+            fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+                let mut namespaces = Vec::new();
+                $( namespaces.extend(<$name as XmlSerialize>::declared_namespaces()); )+
+
+                namespaces
+            }
+        }
+
+        /// Deserializes the members of a tuple from consecutive content nodes.
+        impl<$( $name ),+> XmlDeserialize for ($( $name, )+)
+        where
+            $( $name: XmlDeserialize, )+
+        {
+            fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+                Ok(($( <$name as XmlDeserialize>::deserialize_child_nodes(content)?, )+))
+            }
+        })*
+    };
+}
+
+impl_for_tuple! {
+    (T0.0)
+    (T0.0, T1.1)
+    (T0.0, T1.1, T2.2)
+    (T0.0, T1.1, T2.2, T3.3)
+    (T0.0, T1.1, T2.2, T3.3, T4.4)
+    (T0.0, T1.1, T2.2, T3.3, T4.4, T5.5)
+    (T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6)
+    (T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6, T7.7)
+    (T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6, T7.7, T8.8)
+    (T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6, T7.7, T8.8, T9.9)
+    (T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6, T7.7, T8.8, T9.9, T10.10)
+    (T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6, T7.7, T8.8, T9.9, T10.10, T11.11)
+}
+
+/// Serializes a string as an XML attribute value.
+impl XmlSerializeAttr for str {
+    fn serialize_as_attribute(
+        &self,
+        start_tag: &mut quick_xml::events::BytesStart,
+        name: &str,
+    ) -> Result<(), Error> {
+        start_tag.push_attribute((name, self));
+
+        Ok(())
     }
 }
 
 /// Serializes a string as an XML attribute value.
 impl XmlSerializeAttr for String {
-    fn serialize_as_attribute(&self, start_tag: &mut quick_xml::events::BytesStart, name: &str) {
+    fn serialize_as_attribute(
+        &self,
+        start_tag: &mut quick_xml::events::BytesStart,
+        name: &str,
+    ) -> Result<(), Error> {
         start_tag.push_attribute((name, self.as_str()));
+
+        Ok(())
     }
 }
 
-/// Serializes a string as an XML attribute value.
-impl XmlSerializeAttr for &str {
-    fn serialize_as_attribute(&self, start_tag: &mut quick_xml::events::BytesStart, name: &str) {
-        start_tag.push_attribute((name, *self));
+/// Implements serialization of a pointer or reference type by forwarding to
+/// the implementation for the type it points to.
+///
+/// All methods, including `serialize_as_element()`, are forwarded so that any
+/// specialized behavior of the pointee, such as adding attributes to its
+/// element, is preserved.
+macro_rules! impl_forwarding_for {
+    ($( $ptr:ident $(<$lt:lifetime>)? ),*) => {
+        $(
+        /// Serializes the value pointed to as content nodes.
+        impl<$($lt,)? T> XmlSerialize for impl_forwarding_for!(@ty $ptr $($lt)?)
+        where
+            T: XmlSerialize + ?Sized,
+        {
+            fn serialize_as_element<W>(&self, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
+            where
+                W: std::io::Write,
+            {
+                <T as XmlSerialize>::serialize_as_element(self, writer, name)
+            }
+
+            fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+            where
+                W: std::io::Write,
+            {
+                <T as XmlSerialize>::serialize_child_nodes(self, writer)
+            }
+
+            fn serialize_attributes<W>(
+                &self,
+                writer: &XmlWriter<W>,
+                start_tag: &mut quick_xml::events::BytesStart,
+            ) -> Result<(), Error>
+            where
+                W: std::io::Write,
+            {
+                <T as XmlSerialize>::serialize_attributes(self, writer, start_tag)
+            }
+
+            fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+                <T as XmlSerialize>::declared_namespaces()
+            }
+        }
+
+        /// Serializes the value pointed to as an XML attribute value.
+        impl<$($lt,)? T> XmlSerializeAttr for impl_forwarding_for!(@ty $ptr $($lt)?)
+        where
+            T: XmlSerializeAttr + ?Sized,
+        {
+            fn serialize_as_attribute(
+                &self,
+                start_tag: &mut quick_xml::events::BytesStart,
+                name: &str,
+            ) -> Result<(), Error> {
+                <T as XmlSerializeAttr>::serialize_as_attribute(self, start_tag, name)
+            }
+        })*
+    };
+
+    (@ty Ref $lt:lifetime) => { &$lt T };
+    (@ty $ptr:ident) => { $ptr<T> };
+}
+
+impl_forwarding_for!(Ref<'a>, Box, Rc, Arc);
+
+/// Serializes the borrowed or owned value as content nodes.
+impl<T> XmlSerialize for Cow<'_, T>
+where
+    T: XmlSerialize + ToOwned + ?Sized,
+{
+    fn serialize_as_element<W>(&self, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        <T as XmlSerialize>::serialize_as_element(self, writer, name)
+    }
+
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        <T as XmlSerialize>::serialize_child_nodes(self, writer)
+    }
+
+    fn serialize_attributes<W>(
+        &self,
+        writer: &XmlWriter<W>,
+        start_tag: &mut quick_xml::events::BytesStart,
+    ) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        <T as XmlSerialize>::serialize_attributes(self, writer, start_tag)
+    }
+
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        <T as XmlSerialize>::declared_namespaces()
+    }
+}
+
+/// Serializes the borrowed or owned value as an XML attribute value.
+impl<T> XmlSerializeAttr for Cow<'_, T>
+where
+    T: XmlSerializeAttr + ToOwned + ?Sized,
+{
+    fn serialize_as_attribute(
+        &self,
+        start_tag: &mut quick_xml::events::BytesStart,
+        name: &str,
+    ) -> Result<(), Error> {
+        <T as XmlSerializeAttr>::serialize_as_attribute(self, start_tag, name)
     }
 }
 
@@ -168,10 +519,16 @@ impl XmlSerializeAttr for &str {
 /// `true` is serialized as the string value "true", while `false` is serialized
 /// as the string value "false".
 impl XmlSerializeAttr for bool {
-    fn serialize_as_attribute(&self, start_tag: &mut quick_xml::events::BytesStart, name: &str) {
+    fn serialize_as_attribute(
+        &self,
+        start_tag: &mut quick_xml::events::BytesStart,
+        name: &str,
+    ) -> Result<(), Error> {
         let content = if *self { "true" } else { "false" };
 
         start_tag.push_attribute((name, content));
+
+        Ok(())
     }
 }
 
@@ -182,10 +539,14 @@ impl<T> XmlSerializeAttr for Option<T>
 where
     T: XmlSerializeAttr,
 {
-    fn serialize_as_attribute(&self, start_tag: &mut quick_xml::events::BytesStart, name: &str) {
+    fn serialize_as_attribute(
+        &self,
+        start_tag: &mut quick_xml::events::BytesStart,
+        name: &str,
+    ) -> Result<(), Error> {
         match self {
             Some(value) => value.serialize_as_attribute(start_tag, name),
-            None => (),
+            None => Ok(()),
         }
     }
 }
@@ -202,7 +563,7 @@ macro_rules! impl_as_text_for {
         $(
         /// Serializes an integer as a text content node.
         impl XmlSerialize for $ty {
-            fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+            fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
             where
                 W: std::io::Write,
             {
@@ -219,11 +580,544 @@ macro_rules! impl_as_text_for {
                 &self,
                 start_tag: &mut quick_xml::events::BytesStart,
                 name: &str,
-            ) {
+            ) -> Result<(), Error> {
                 start_tag.push_attribute((name, self.to_string().as_str()));
+
+                Ok(())
+            }
+        })*
+    };
+}
+
+impl_as_text_for!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+/// Implements serialization of a floating-point type as an XML text node or
+/// attribute value.
+///
+/// Values are written in the lexical form of the XML Schema `float` and
+/// `double` types, using the shortest decimal representation which
+/// round-trips. Zero and finite values with a magnitude of at least `1e-7` and
+/// less than `1e21` are written without an exponent (e.g. `1.5`, `-0`,
+/// `100000000000000000000`, or `0.0000001`). Other finite values are written
+/// with an uppercase `E` exponent and a mantissa with at least one fractional
+/// digit (e.g. `1.0E21`, `-1.5E300`, or `1.0E-8`). Infinite values are written
+/// as `INF` or `-INF` and NaN is written as `NaN`.
+macro_rules! impl_float_as_text_for {
+    ($( $ty:ty ),*) => {
+        $(
+        /// Serializes a floating-point number as a text content node.
+        impl XmlSerialize for $ty {
+            fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+            where
+                W: std::io::Write,
+            {
+                let string = float_to_text(*self);
+                writer.write_event(Event::Text(BytesText::new(&string)))?;
+
+                Ok(())
+            }
+        }
+
+        /// Serializes a floating-point number as an XML attribute value.
+        impl XmlSerializeAttr for $ty {
+            fn serialize_as_attribute(
+                &self,
+                start_tag: &mut quick_xml::events::BytesStart,
+                name: &str,
+            ) -> Result<(), Error> {
+                start_tag.push_attribute((name, float_to_text(*self).as_str()));
+
+                Ok(())
+            }
+        })*
+    };
+}
+
+impl_float_as_text_for!(f32, f64);
+
+/// The magnitude at or above which floating-point numbers are formatted with an
+/// exponent.
+const FLOAT_EXPONENT_UPPER_BOUND: f64 = 1e21;
+
+/// The magnitude below which nonzero floating-point numbers are formatted with
+/// an exponent.
+const FLOAT_EXPONENT_LOWER_BOUND: f64 = 1e-7;
+
+/// Formats a floating-point number in its XML Schema lexical representation.
+fn float_to_text<T>(value: T) -> String
+where
+    T: Copy + Into<f64> + std::fmt::Display + std::fmt::UpperExp,
+{
+    let as_f64: f64 = value.into();
+    let magnitude = as_f64.abs();
+
+    if as_f64.is_nan() {
+        String::from("NaN")
+    } else if as_f64 == f64::INFINITY {
+        String::from("INF")
+    } else if as_f64 == f64::NEG_INFINITY {
+        String::from("-INF")
+    } else if magnitude >= FLOAT_EXPONENT_UPPER_BOUND
+        || (magnitude != 0.0 && magnitude < FLOAT_EXPONENT_LOWER_BOUND)
+    {
+        // Without an exponent, very large or small numbers would be written
+        // out in hundreds of digits. Rust's `UpperExp` implementation also
+        // produces the shortest round-trip representation, but omits the
+        // fractional part of the mantissa when it's zero, which we restore
+        // to match the canonical form, e.g. `1.0E300`.
+        let text = format!("{value:E}");
+        match text.split_once('E') {
+            Some((mantissa, exponent)) if !mantissa.contains('.') => {
+                format!("{mantissa}.0E{exponent}")
+            }
+            _ => text,
+        }
+    } else {
+        // Rust's `Display` implementation for floats produces the shortest
+        // round-trip representation without an exponent, which is a valid
+        // XML Schema lexical form.
+        value.to_string()
+    }
+}
+
+/// Serializes a character as a text content node.
+impl XmlSerialize for char {
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        let mut buf = [0; 4];
+        writer.write_event(Event::Text(BytesText::new(self.encode_utf8(&mut buf))))?;
+
+        Ok(())
+    }
+}
+
+/// Serializes a character as an XML attribute value.
+impl XmlSerializeAttr for char {
+    fn serialize_as_attribute(
+        &self,
+        start_tag: &mut quick_xml::events::BytesStart,
+        name: &str,
+    ) -> Result<(), Error> {
+        let mut buf = [0; 4];
+        start_tag.push_attribute((name, &*self.encode_utf8(&mut buf)));
+
+        Ok(())
+    }
+}
+
+/// Deserializes a string from text content nodes.
+///
+/// If there are no text content nodes, an empty string is produced.
+impl XmlDeserialize for String {
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        Ok(content.take_text())
+    }
+}
+
+/// Deserializes a boolean from a text content node.
+///
+/// In accordance with the XML Schema `boolean` type, the string values "true"
+/// and "1" are deserialized as `true`, while "false" and "0" are deserialized
+/// as `false`.
+impl XmlDeserialize for bool {
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        bool_from_text(&content.take_text())
+    }
+}
+
+/// Deserializes an `Option<T>` from content nodes.
+///
+/// When deserialized as an element, `None` is produced if the next content node
+/// is not an element of the expected name. Otherwise, `None` is produced if
+/// there are no remaining content nodes.
+impl<T> XmlDeserialize for Option<T>
+where
+    T: XmlDeserialize,
+{
+    fn deserialize_as_element(content: &mut Content<'_>, name: &str) -> Result<Self, Error> {
+        content
+            .next_element_if_named(name)
+            .map(|element| {
+                <T as XmlDeserialize>::deserialize_from_element(element)
+                    .map_err(|err| err.in_element(name))
+            })
+            .transpose()
+    }
+
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        <T as XmlDeserialize>::deserialize_child_nodes(content).map(Some)
+    }
+}
+
+/// Deserializes a `Vec<T>` from content nodes.
+///
+/// Elements are deserialized via `T`'s `deserialize_child_nodes()`
+/// implementation until all content nodes are consumed.
+impl<T> XmlDeserialize for Vec<T>
+where
+    T: XmlDeserialize,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        deserialize_each(content)
+    }
+}
+
+/// Deserializes an array from content nodes.
+///
+/// Elements are deserialized as for `Vec<T>`, and the number of elements must
+/// match the length of the array exactly.
+impl<T, const N: usize> XmlDeserialize for [T; N]
+where
+    T: XmlDeserialize,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        let values: Vec<T> = deserialize_each(content)?;
+
+        values.try_into().map_err(|values: Vec<T>| {
+            Error::UnexpectedStructure(format!(
+                "expected {N} values in `{}`, found {}",
+                content.element().name(),
+                values.len()
+            ))
+        })
+    }
+}
+
+/// Deserializes a `VecDeque<T>` from content nodes.
+///
+/// Elements are deserialized as for `Vec<T>`, from front to back.
+impl<T> XmlDeserialize for VecDeque<T>
+where
+    T: XmlDeserialize,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        deserialize_each(content)
+    }
+}
+
+/// Deserializes a `BTreeSet<T>` from content nodes.
+///
+/// Elements are deserialized as for `Vec<T>`. Duplicate elements are merged.
+impl<T> XmlDeserialize for BTreeSet<T>
+where
+    T: XmlDeserialize + Ord,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        deserialize_each(content)
+    }
+}
+
+/// Deserializes a `HashSet<T>` from content nodes.
+///
+/// Elements are deserialized as for `Vec<T>`. Duplicate elements are merged.
+impl<T, S> XmlDeserialize for HashSet<T, S>
+where
+    T: XmlDeserialize + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        deserialize_each(content)
+    }
+}
+
+/// Deserializes a `BTreeMap<K, V>` from content nodes.
+///
+/// Each element is deserialized as a value keyed by the element's name.
+impl<K, V> XmlDeserialize for BTreeMap<K, V>
+where
+    K: From<String> + Ord,
+    V: XmlDeserialize,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        deserialize_keyed_elements(content)
+    }
+}
+
+/// Deserializes a `HashMap<K, V>` from content nodes.
+///
+/// Each element is deserialized as a value keyed by the element's name.
+impl<K, V, S> XmlDeserialize for HashMap<K, V, S>
+where
+    K: From<String> + Eq + Hash,
+    V: XmlDeserialize,
+    S: BuildHasher + Default,
+{
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        deserialize_keyed_elements(content)
+    }
+}
+
+/// Deserializes each remaining element as a value keyed by its name.
+fn deserialize_keyed_elements<K, V, C>(content: &mut Content<'_>) -> Result<C, Error>
+where
+    K: From<String>,
+    V: XmlDeserialize,
+    C: FromIterator<(K, V)>,
+{
+    let mut entries = Vec::new();
+    while !content.is_empty() {
+        let element = content.next_element()?;
+        let value = <V as XmlDeserialize>::deserialize_from_element(element)
+            .map_err(|err| err.in_element(element.name()))?;
+
+        entries.push((K::from(element.name().to_owned()), value));
+    }
+
+    Ok(entries.into_iter().collect())
+}
+
+/// Deserializes values via `T`'s `deserialize_child_nodes()` implementation
+/// until all content nodes are consumed.
+fn deserialize_each<T, C>(content: &mut Content<'_>) -> Result<C, Error>
+where
+    T: XmlDeserialize,
+    C: FromIterator<T>,
+{
+    let mut values = Vec::new();
+
+    while !content.is_empty() {
+        let position = content.position();
+        values.push(<T as XmlDeserialize>::deserialize_child_nodes(content)?);
+
+        // If `T` doesn't consume any content, we would otherwise loop
+        // forever.
+        if content.position() == position {
+            return Err(Error::UnexpectedStructure(format!(
+                "unexpected content in `{}`",
+                content.element().name()
+            )));
+        }
+    }
+
+    Ok(values.into_iter().collect())
+}
+
+/// Deserializes a string from an XML attribute value.
+impl XmlDeserializeAttr for String {
+    fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+        Ok(value.to_owned())
+    }
+}
+
+/// Deserializes a boolean from an XML attribute value.
+///
+/// Accepted values are as for deserialization from a text content node.
+impl XmlDeserializeAttr for bool {
+    fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+        bool_from_text(value)
+    }
+}
+
+/// Deserializes an `Option<T>` from an XML attribute value.
+///
+/// If the attribute is not present, `None` is produced.
+impl<T> XmlDeserializeAttr for Option<T>
+where
+    T: XmlDeserializeAttr,
+{
+    fn deserialize_from_attribute(element: &Element, name: &str) -> Result<Self, Error> {
+        element
+            .attribute(name)
+            .map(<T as XmlDeserializeAttr>::deserialize_from_attribute_value)
+            .transpose()
+    }
+
+    fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+        <T as XmlDeserializeAttr>::deserialize_from_attribute_value(value).map(Some)
+    }
+}
+
+/// Implements deserialization of a smart pointer type by forwarding to the
+/// implementation for the type it points to.
+macro_rules! impl_deserialize_forwarding_for {
+    ($( $ptr:ident ),*) => {
+        $(
+        /// Deserializes the value pointed to from content nodes.
+        impl<T> XmlDeserialize for $ptr<T>
+        where
+            T: XmlDeserialize,
+        {
+            fn deserialize_as_element(content: &mut Content<'_>, name: &str) -> Result<Self, Error> {
+                <T as XmlDeserialize>::deserialize_as_element(content, name).map($ptr::new)
+            }
+
+            fn deserialize_from_element(element: &Element) -> Result<Self, Error> {
+                <T as XmlDeserialize>::deserialize_from_element(element).map($ptr::new)
+            }
+
+            fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+                <T as XmlDeserialize>::deserialize_child_nodes(content).map($ptr::new)
+            }
+        }
+
+        /// Deserializes the value pointed to from an XML attribute value.
+        impl<T> XmlDeserializeAttr for $ptr<T>
+        where
+            T: XmlDeserializeAttr,
+        {
+            fn deserialize_from_attribute(element: &Element, name: &str) -> Result<Self, Error> {
+                <T as XmlDeserializeAttr>::deserialize_from_attribute(element, name).map($ptr::new)
+            }
+
+            fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+                <T as XmlDeserializeAttr>::deserialize_from_attribute_value(value).map($ptr::new)
+            }
+        })*
+    };
+}
+
+impl_deserialize_forwarding_for!(Box, Rc, Arc);
+
+/// Deserializes an owned value from content nodes.
+impl<T> XmlDeserialize for Cow<'_, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: XmlDeserialize,
+{
+    fn deserialize_as_element(content: &mut Content<'_>, name: &str) -> Result<Self, Error> {
+        <T::Owned as XmlDeserialize>::deserialize_as_element(content, name).map(Cow::Owned)
+    }
+
+    fn deserialize_from_element(element: &Element) -> Result<Self, Error> {
+        <T::Owned as XmlDeserialize>::deserialize_from_element(element).map(Cow::Owned)
+    }
+
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        <T::Owned as XmlDeserialize>::deserialize_child_nodes(content).map(Cow::Owned)
+    }
+}
+
+/// Deserializes an owned value from an XML attribute value.
+impl<T> XmlDeserializeAttr for Cow<'_, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: XmlDeserializeAttr,
+{
+    fn deserialize_from_attribute(element: &Element, name: &str) -> Result<Self, Error> {
+        <T::Owned as XmlDeserializeAttr>::deserialize_from_attribute(element, name).map(Cow::Owned)
+    }
+
+    fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+        <T::Owned as XmlDeserializeAttr>::deserialize_from_attribute_value(value).map(Cow::Owned)
+    }
+}
+
+/// Parses a boolean from its XML Schema lexical representation.
+fn bool_from_text(text: &str) -> Result<bool, Error> {
+    match text.trim() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        other => Err(Error::Value(anyhow::anyhow!(
+            "invalid boolean value `{other}`"
+        ))),
+    }
+}
+
+/// Implements deserialization of a type from either an XML text node or
+/// attribute value.
+///
+/// As with `impl_as_text_for!`, this is intended for primitive/standard library
+/// types and relies on their `FromStr` implementations.
+macro_rules! impl_from_text_for {
+    ($( $ty:ty ),*) => {
+        $(
+        /// Deserializes an integer from a text content node.
+        impl XmlDeserialize for $ty {
+            fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+                let text = content.take_text();
+
+                <$ty as XmlDeserializeAttr>::deserialize_from_attribute_value(&text)
+            }
+        }
+
+        /// Deserializes an integer from an XML attribute value.
+        impl XmlDeserializeAttr for $ty {
+            fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|err| Error::Value(anyhow::Error::new(err)))
             }
         })*
     };
 }
 
-impl_as_text_for!(i8, u8, i16, u16, i32, u32, i64, u64, usize);
+impl_from_text_for!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+/// Implements deserialization of a floating-point type from either an XML text
+/// node or attribute value.
+///
+/// Values are accepted in the lexical form of the XML Schema `float` and
+/// `double` types, i.e. decimal numbers with an optional exponent, `INF`,
+/// `+INF`, `-INF`, or `NaN`. Rust-specific forms such as `inf` or `infinity`
+/// are rejected.
+macro_rules! impl_float_from_text_for {
+    ($( $ty:ty ),*) => {
+        $(
+        /// Deserializes a floating-point number from a text content node.
+        impl XmlDeserialize for $ty {
+            fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+                let text = content.take_text();
+
+                <$ty as XmlDeserializeAttr>::deserialize_from_attribute_value(&text)
+            }
+        }
+
+        /// Deserializes a floating-point number from an XML attribute value.
+        impl XmlDeserializeAttr for $ty {
+            fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+                match value.trim() {
+                    "INF" | "+INF" => Ok(<$ty>::INFINITY),
+                    "-INF" => Ok(<$ty>::NEG_INFINITY),
+                    "NaN" => Ok(<$ty>::NAN),
+
+                    // Rust accepts special values in forms XML Schema does
+                    // not, so anything remaining must be purely numeric.
+                    other if other.contains(|c: char| c.is_ascii_alphabetic() && c != 'e' && c != 'E') => {
+                        Err(Error::Value(anyhow::anyhow!(
+                            "invalid floating-point value `{other}`"
+                        )))
+                    }
+                    other => other
+                        .parse()
+                        .map_err(|err| Error::Value(anyhow::Error::new(err))),
+                }
+            }
+        })*
+    };
+}
+
+impl_float_from_text_for!(f32, f64);
+
+/// Deserializes a character from a text content node.
+///
+/// The text must consist of exactly one character. Whitespace is significant.
+impl XmlDeserialize for char {
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error> {
+        let text = content.take_text();
+
+        <char as XmlDeserializeAttr>::deserialize_from_attribute_value(&text)
+    }
+}
+
+/// Deserializes a character from an XML attribute value.
+///
+/// The value must consist of exactly one character. Whitespace is significant.
+impl XmlDeserializeAttr for char {
+    fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error> {
+        let mut chars = value.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(Error::Value(anyhow::anyhow!(
+                "expected a single character, got `{value}`"
+            ))),
+        }
+    }
+}
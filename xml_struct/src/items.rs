@@ -0,0 +1,110 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! This module provides a helper for representing collections as a list of
+//! individually-named elements.
+
+use crate::{Content, Error, XmlDeserialize, XmlSerialize, XmlWriter};
+
+/// A view of a collection which serializes each of its entries as an XML
+/// element with a common name.
+///
+/// For example, serializing `Items::new("ItemId", &ids)` as an element named
+/// "ItemIds" produces output like the following:
+///
+/// ```text
+/// <ItemIds>
+///     <ItemId>...</ItemId>
+///     <ItemId>...</ItemId>
+/// </ItemIds>
+/// ```
+///
+/// Because the item name isn't part of the collection's type, deserialization
+/// is provided via associated functions which take the item name as an
+/// argument rather than via an implementation of `XmlDeserialize`.
+#[derive(Debug)]
+pub struct Items<'a, T> {
+    item_name: &'a str,
+    items: &'a [T],
+}
+
+impl<'a, T> Items<'a, T> {
+    /// Creates a view of a collection with each entry named `item_name`.
+    pub fn new(item_name: &'a str, items: &'a [T]) -> Self {
+        Self { item_name, items }
+    }
+}
+
+impl<T> Items<'_, T>
+where
+    T: XmlDeserialize,
+{
+    /// Deserializes a collection from an XML element named `name` containing
+    /// only elements named `item_name`.
+    pub fn deserialize_as_element<C>(
+        content: &mut Content<'_>,
+        name: &str,
+        item_name: &str,
+    ) -> Result<C, Error>
+    where
+        C: FromIterator<T>,
+    {
+        let element = content.next_element_named(name)?;
+
+        Self::deserialize_child_nodes(&mut element.content(), item_name)
+            .map_err(|err| err.in_element(name))
+    }
+
+    /// Deserializes a collection from content nodes consisting only of elements
+    /// named `item_name`.
+    pub fn deserialize_child_nodes<C>(
+        content: &mut Content<'_>,
+        item_name: &str,
+    ) -> Result<C, Error>
+    where
+        C: FromIterator<T>,
+    {
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            let position = content.position();
+            items.push(T::deserialize_as_element(content, item_name)?);
+
+            // If `T` doesn't consume an element, e.g. an `Option` not matching
+            // the item name, we would otherwise loop forever.
+            if content.position() == position {
+                return Err(Error::UnexpectedStructure(format!(
+                    "unexpected content in `{}`",
+                    content.element().name()
+                )));
+            }
+        }
+
+        Ok(items.into_iter().collect())
+    }
+}
+
+impl<T> Clone for Items<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Items<'_, T> {}
+
+/// Serializes each entry of a collection as an element named for the item.
+impl<T> XmlSerialize for Items<'_, T>
+where
+    T: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        for item in self.items {
+            item.serialize_as_element(writer, self.item_name)?;
+        }
+
+        Ok(())
+    }
+}
@@ -3,43 +3,74 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 //! This crate provides a mechanism for serializing Rust data structures as
-//! well-formed XML with a minimum of boilerplate.
+//! well-formed XML with a minimum of boilerplate, and for deserializing them
+//! from XML of the same form.
 //!
-//! Consumers can provide manual implementations of the [`XmlSerialize`] and
-//! [`XmlSerializeAttr`] traits if desired, but the primary intent of this crate
-//! is to provide automated derivation of these traits in order to facilitate
-//! serialization of complex XML structures.
+//! Consumers can provide manual implementations of the [`XmlSerialize`],
+//! [`XmlSerializeAttr`], [`XmlDeserialize`], and [`XmlDeserializeAttr`] traits
+//! if desired, but the primary intent of this crate is to provide automated
+//! derivation of these traits in order to facilitate serialization of complex
+//! XML structures.
+//!
+//! # Migrating from `quick_xml::Writer`
+//!
+//! Values are serialized to an [`XmlWriter`], which tracks the namespace
+//! declarations in scope in the document being written, rather than directly to
+//! a `quick_xml::Writer`. Manual implementations of [`XmlSerialize`] and
+//! functions given with `serialize_with` should take `&mut XmlWriter<W>` in
+//! place of `&mut quick_xml::Writer<W>`; [`XmlWriter::write_event`] accepts
+//! the same events, though it returns this crate's [`Error`]. Existing writers
+//! may be converted with [`From`], and the output retrieved with
+//! [`XmlWriter::into_inner`] as before. Writers may also be put in strict mode
+//! with [`XmlWriter::strict`] to check that their output is well-formed.
 //!
 //! # Limitations
 //!
 //! At present, derived implementations of these traits are designed to handle
-//! the specific case of Microsoft Exchange Web Services. As such, all XML
-//! elements and attributes are named in PascalCase and certain behaviors are
+//! the specific case of Microsoft Exchange Web Services. As such, XML elements
+//! and attributes are named in PascalCase by default and certain behaviors are
 //! not supported (such as serializing enum variants without enclosing XML
 //! elements derived from the variant name).
 //!
-//! Furthermore, the PascalCase implementation is naïve and depends on
-//! [`char::to_ascii_uppercase`], making it unsuitable for use with non-ASCII
-//! identifiers.
+//! Furthermore, case conversion of names is naïve and depends on
+//! [`char::to_ascii_uppercase`] and [`char::to_ascii_lowercase`], making it
+//! unsuitable for use with non-ASCII identifiers.
 //!
-//! There is also currently no provision for deserialization from XML, as the
-//! support offered by `quick_xml`'s serde implementation has been found to be
-//! sufficient for the time being.
+//! Deserialization reads each element into memory in full before converting it
+//! to a data structure. Names given with a namespace prefix are matched exactly
+//! as written; only names in a namespace given by name are matched regardless
+//! of prefix.
 //!
 //! In recognition of these limitations, this crate should not be published to
 //! crates.io at this time. If a generalized implementation generates interest
 //! or is thought to have merit, these limitations may be addressed at a later
 //! time.
 
+mod attr_list;
+mod cdata;
 mod impls;
+mod items;
+mod map;
+mod namespaces;
+mod reader;
 mod tests;
+mod writer;
+
+use std::io::BufRead;
 
 use quick_xml::{
     events::{BytesEnd, BytesStart, Event},
-    Writer,
+    Reader,
 };
 use thiserror::Error;
 
+pub use attr_list::AttrList;
+pub use cdata::Cdata;
+pub use items::Items;
+pub use map::{MapAttributes, MapEntries, XmlMap};
+pub use namespaces::QName;
+pub use reader::{Content, Element, Node};
+pub use writer::XmlWriter;
 pub use xml_struct_derive::*;
 
 /// A data structure which can be serialized as XML content nodes.
@@ -50,11 +81,8 @@ pub use xml_struct_derive::*;
 /// derived and manual implementations.
 ///
 /// ```
-/// use quick_xml::{
-///     events::{BytesText, Event},
-///     writer::Writer
-/// };
-/// use xml_struct::{Error, XmlSerialize};
+/// use quick_xml::events::{BytesText, Event};
+/// use xml_struct::{Error, XmlWriter, XmlSerialize};
 ///
 /// #[derive(XmlSerialize)]
 /// #[xml_struct(default_ns = "http://foo.example/")]
@@ -71,7 +99,7 @@ pub use xml_struct_derive::*;
 /// }
 ///
 /// impl XmlSerialize for Bar {
-///     fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+///     fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
 ///     where
 ///         W: std::io::Write,
 ///     {
@@ -84,7 +112,7 @@ pub use xml_struct_derive::*;
 ///     }
 /// }
 ///
-/// let mut writer: Writer<Vec<u8>> = Writer::new(Vec::new());
+/// let mut writer: XmlWriter<Vec<u8>> = XmlWriter::new(Vec::new());
 /// let foo = Foo {
 ///     some_field: "foo".into(),
 ///     something_else: Bar::Baz,
@@ -103,32 +131,190 @@ pub use xml_struct_derive::*;
 pub trait XmlSerialize {
     /// Serializes this value as XML content nodes within an enclosing XML
     /// element.
-    fn serialize_as_element<W>(&self, writer: &mut Writer<W>, name: &str) -> Result<(), Error>
+    fn serialize_as_element<W>(&self, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
     where
         W: std::io::Write,
     {
-        writer.write_event(Event::Start(BytesStart::new(name)))?;
+        let mut start_tag = BytesStart::new(name);
+        writer.declare_namespaces(&mut start_tag, Self::declared_namespaces());
+
+        self.serialize_attributes(writer, &mut start_tag)
+            .map_err(|err| err.in_element(name))?;
+
+        writer.write_event(Event::Start(start_tag))?;
 
-        self.serialize_child_nodes(writer)?;
+        self.serialize_child_nodes(writer)
+            .map_err(|err| err.in_element(name))?;
 
         writer.write_event(Event::End(BytesEnd::new(name)))?;
 
         Ok(())
     }
 
+    /// Serializes this value as XML content nodes within an enclosing XML
+    /// element named in a namespace.
+    ///
+    /// The element is named with a prefix bound to its namespace, reusing one
+    /// in scope where possible and otherwise declaring one on the element
+    /// itself.
+    fn serialize_as_qualified_element<W>(
+        &self,
+        writer: &mut XmlWriter<W>,
+        name: QName<'_>,
+    ) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        writer.with_element_name(name, |writer, name| self.serialize_as_element(writer, name))
+    }
+
     /// Serializes this value as XML content nodes.
-    fn serialize_child_nodes<W>(&self, writer: &mut Writer<W>) -> Result<(), Error>
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
     where
         W: std::io::Write;
+
+    /// Adds the attributes of this value to the start tag of the element
+    /// enclosing its content nodes.
+    ///
+    /// Content nodes have no start tag of their own on which to set
+    /// attributes, so when this value is serialized as content nodes, e.g. as
+    /// a flattened field, its attributes are set on the enclosing element. The
+    /// default implementation of `serialize_as_element()` sets them on the
+    /// value's own element. This mirrors deserialization, in which values read
+    /// from content nodes read their attributes from the enclosing element.
+    ///
+    /// Types which have no attributes need not implement this.
+    fn serialize_attributes<W>(
+        &self,
+        _writer: &XmlWriter<W>,
+        _start_tag: &mut BytesStart,
+    ) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        Ok(())
+    }
+
+    /// Gets the namespace declarations required by this type's content nodes,
+    /// as pairs of a prefix, or `None` for the default namespace, and the
+    /// namespace name to which it is bound.
+    ///
+    /// Content nodes have no start tag of their own on which to declare
+    /// namespaces, so these declarations are made on the element enclosing
+    /// them, e.g. the element of a struct with a flattened field of this type.
+    /// The default implementation of `serialize_as_element()` makes them on the
+    /// value's own element.
+    ///
+    /// A declaration of the default namespace is only ever made on the value's
+    /// own element, as making it on an enclosing element would change the
+    /// namespace of that element and its unprefixed children.
+    ///
+    /// Types which write no prefixed names outside of elements making their own
+    /// declarations need not implement this.
+    fn declared_namespaces() -> Vec<(Option<&'static str>, &'static str)> {
+        Vec::new()
+    }
 }
 
 /// A data structure which can be serialized as the value of an XML attribute.
 pub trait XmlSerializeAttr {
     /// Serializes this value as the value of an XML attribute.
-    fn serialize_as_attribute(&self, start_tag: &mut BytesStart, name: &str);
+    ///
+    /// Implementations may add no attribute at all if the value has no
+    /// attribute representation, e.g. `None`.
+    fn serialize_as_attribute(&self, start_tag: &mut BytesStart, name: &str) -> Result<(), Error>;
+}
+
+/// A data structure which can be deserialized from XML content nodes.
+///
+/// # Usage
+///
+/// The following demonstrates a round trip through derived implementations of
+/// `XmlSerialize` and `XmlDeserialize`.
+///
+/// ```
+/// use quick_xml::reader::Reader;
+/// use xml_struct::{XmlWriter, XmlDeserialize, XmlSerialize};
+///
+/// #[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+/// struct Foo {
+///     #[xml_struct(attribute)]
+///     id: u32,
+///
+///     some_field: String,
+/// }
+///
+/// let mut writer: XmlWriter<Vec<u8>> = XmlWriter::new(Vec::new());
+/// let foo = Foo {
+///     id: 17,
+///     some_field: "foo".into(),
+/// };
+///
+/// assert!(foo.serialize_as_element(&mut writer, "Foo").is_ok());
+///
+/// let out = writer.into_inner();
+/// assert_eq!(
+///     std::str::from_utf8(&out).unwrap(),
+///     r#"<Foo Id="17"><SomeField>foo</SomeField></Foo>"#,
+/// );
+///
+/// let mut reader = Reader::from_reader(out.as_slice());
+/// let actual = Foo::deserialize_from_reader(&mut reader).unwrap();
+///
+/// assert_eq!(actual, foo);
+/// ```
+pub trait XmlDeserialize: Sized {
+    /// Reads the next complete element from an XML document and deserializes
+    /// a value from it.
+    ///
+    /// The name of the element is not checked.
+    fn deserialize_from_reader<R>(reader: &mut Reader<R>) -> Result<Self, Error>
+    where
+        R: BufRead,
+    {
+        let element = Element::read_from(reader)?;
+
+        Self::deserialize_from_element(&element).map_err(|err| err.in_element(element.name()))
+    }
+
+    /// Deserializes a value from the next content node, which must be an XML
+    /// element with the given name.
+    fn deserialize_as_element(content: &mut Content<'_>, name: &str) -> Result<Self, Error> {
+        let element = content.next_element_named(name)?;
+
+        Self::deserialize_from_element(element).map_err(|err| err.in_element(name))
+    }
+
+    /// Deserializes a value from the attributes and content nodes of an XML
+    /// element.
+    fn deserialize_from_element(element: &Element) -> Result<Self, Error> {
+        Self::deserialize_child_nodes(&mut element.content())
+    }
+
+    /// Deserializes a value from XML content nodes, consuming those nodes which
+    /// represent it.
+    fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Self, Error>;
+}
+
+/// A data structure which can be deserialized from the value of an XML
+/// attribute.
+pub trait XmlDeserializeAttr: Sized {
+    /// Deserializes a value from the named attribute of an XML element.
+    fn deserialize_from_attribute(element: &Element, name: &str) -> Result<Self, Error> {
+        match element.attribute(name) {
+            Some(value) => Self::deserialize_from_attribute_value(value),
+            None => Err(Error::UnexpectedStructure(format!(
+                "missing attribute `{name}` on `{}`",
+                element.name()
+            ))),
+        }
+    }
+
+    /// Deserializes a value from the unescaped value of an XML attribute.
+    fn deserialize_from_attribute_value(value: &str) -> Result<Self, Error>;
 }
 
-/// An error generated during the XML serialization process.
+/// An error generated during the XML serialization or deserialization process.
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum Error {
@@ -136,8 +322,138 @@ pub enum Error {
     Xml(#[from] quick_xml::Error),
 
     /// An error representing a failure in formatting a data structure prior to
-    /// serializing it into XML. Its inner type is generic on purpose, as the
-    /// specific error type might be defined by a third-party crate.
-    #[error("failed to serialize value as text")]
+    /// serializing it into XML, or in parsing one from text. Its inner type is
+    /// generic on purpose, as the specific error type might be defined by a
+    /// third-party crate.
+    #[error("failed to convert value to or from text")]
     Value(#[from] anyhow::Error),
+
+    /// An error representing XML content which does not match the structure
+    /// expected by the data structure being deserialized.
+    #[error("unexpected XML structure: {0}")]
+    UnexpectedStructure(String),
+
+    /// An error representing a name or text which cannot appear in
+    /// well-formed XML, detected when serializing, e.g. by a writer in strict
+    /// mode (see [`XmlWriter::strict`]).
+    #[error("output would not be well-formed XML: {0}")]
+    IllFormed(String),
+
+    /// An error which occurred within a particular part of an XML document,
+    /// annotated with its location.
+    ///
+    /// Derived implementations attach context to any error produced by one of
+    /// their fields, and each enclosing element adds its name to the path as
+    /// the error is returned.
+    #[error("error at {context}")]
+    Context {
+        context: Box<ErrorContext>,
+
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Gets the location at which this error occurred, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Gets the underlying error, without any location context.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Self::Context { source, .. } => source.root_cause(),
+            _ => self,
+        }
+    }
+
+    /// Adds the name of an element enclosing the location of this error to
+    /// its path.
+    pub fn in_element(self, name: &str) -> Self {
+        self.with_path_segment(name.to_owned())
+    }
+
+    /// Adds the name of the attribute at which this error occurred to its
+    /// path.
+    pub fn in_attribute(self, name: &str) -> Self {
+        self.with_path_segment(format!("@{name}"))
+    }
+
+    /// Records the Rust type and field in which this error occurred.
+    ///
+    /// Only the innermost field is recorded, so this has no effect if the
+    /// error already has an associated field.
+    pub fn in_field(self, type_name: &'static str, field_name: &'static str) -> Self {
+        let (mut context, source) = self.into_context_parts();
+        context.field.get_or_insert((type_name, field_name));
+
+        Self::Context { context, source }
+    }
+
+    /// Prepends a segment to the path of this error.
+    fn with_path_segment(self, segment: String) -> Self {
+        let (mut context, source) = self.into_context_parts();
+        context.path.insert(0, segment);
+
+        Self::Context { context, source }
+    }
+
+    /// Splits this error into its context, which is empty if there was none,
+    /// and the error it describes.
+    fn into_context_parts(self) -> (Box<ErrorContext>, Box<Error>) {
+        match self {
+            Self::Context { context, source } => (context, source),
+            err => (Box::default(), Box::new(err)),
+        }
+    }
+}
+
+/// The location in an XML document and in Rust data structures at which an
+/// error occurred.
+#[derive(Debug, Default)]
+pub struct ErrorContext {
+    /// The names of the elements enclosing the error's location, outermost
+    /// first, followed by the attribute name if the error occurred in an
+    /// attribute.
+    path: Vec<String>,
+
+    /// The name of the type and of the field in which the error occurred.
+    field: Option<(&'static str, &'static str)>,
+}
+
+impl ErrorContext {
+    /// Gets the path to the error's location, e.g.
+    /// `/CreateItem/Items/Message/Body` or `/Message/@Id`.
+    pub fn path(&self) -> String {
+        format!("/{}", self.path.join("/"))
+    }
+
+    /// Gets the name of the Rust type containing the field in which the error
+    /// occurred, if known.
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.field.map(|(type_name, _)| type_name)
+    }
+
+    /// Gets the name of the Rust field in which the error occurred, if known.
+    ///
+    /// Unnamed fields are named by their position, e.g. `0`.
+    pub fn field_name(&self) -> Option<&'static str> {
+        self.field.map(|(_, field_name)| field_name)
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.path())?;
+
+        if let Some((type_name, field_name)) = self.field {
+            write!(f, " (field `{field_name}` of `{type_name}`)")?;
+        }
+
+        Ok(())
+    }
 }
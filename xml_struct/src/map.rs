@@ -0,0 +1,254 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! This module provides support for representing maps as keyed XML elements or
+//! as sets of attributes.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+
+use crate::{
+    Content, Element, Error, XmlDeserialize, XmlDeserializeAttr, XmlSerialize, XmlSerializeAttr,
+    XmlWriter,
+};
+
+/// A collection of key/value pairs which can be represented in XML.
+///
+/// This trait is implemented for `BTreeMap` and `HashMap`. Entries of a
+/// `BTreeMap` are serialized in ascending key order, while entries of a
+/// `HashMap` are serialized in its iteration order, which is unspecified and
+/// may differ between runs.
+pub trait XmlMap: Sized {
+    type Key;
+    type Value;
+
+    /// Gets an iterator over the entries of the map.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&Self::Key, &Self::Value)> + '_>;
+
+    /// Constructs a map from key/value pairs.
+    fn from_entries<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (Self::Key, Self::Value)>;
+}
+
+impl<K, V> XmlMap for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Value = V;
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(self.iter())
+    }
+
+    fn from_entries<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        entries.into_iter().collect()
+    }
+}
+
+impl<K, V, S> XmlMap for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Key = K;
+    type Value = V;
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(self.iter())
+    }
+
+    fn from_entries<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        entries.into_iter().collect()
+    }
+}
+
+/// A view of a map which serializes each of its entries as an XML element with
+/// a common name and the key as an attribute, e.g.:
+///
+/// ```text
+/// <Entry Key="first">...</Entry>
+/// <Entry Key="second">...</Entry>
+/// ```
+///
+/// Each value is serialized as the content nodes of its entry element, so any
+/// attributes the value would add to its own element are not represented.
+#[derive(Debug)]
+pub struct MapEntries<'a, M> {
+    map: &'a M,
+    entry_name: &'a str,
+    key_name: &'a str,
+}
+
+impl<'a, M> MapEntries<'a, M> {
+    /// Creates a view of a map with each entry named `entry_name` and its key
+    /// given by the attribute `key_name`.
+    pub fn new(map: &'a M, entry_name: &'a str, key_name: &'a str) -> Self {
+        Self {
+            map,
+            entry_name,
+            key_name,
+        }
+    }
+}
+
+impl<M> MapEntries<'_, M>
+where
+    M: XmlMap,
+    M::Key: XmlDeserializeAttr,
+    M::Value: XmlDeserialize,
+{
+    /// Deserializes a map from an XML element named `name` containing only
+    /// entry elements.
+    pub fn deserialize_as_element(
+        content: &mut Content<'_>,
+        name: &str,
+        entry_name: &str,
+        key_name: &str,
+    ) -> Result<M, Error> {
+        let element = content.next_element_named(name)?;
+
+        Self::deserialize_child_nodes(&mut element.content(), entry_name, key_name)
+            .map_err(|err| err.in_element(name))
+    }
+
+    /// Deserializes a map from content nodes consisting only of entry elements.
+    pub fn deserialize_child_nodes(
+        content: &mut Content<'_>,
+        entry_name: &str,
+        key_name: &str,
+    ) -> Result<M, Error> {
+        let mut entries = Vec::new();
+        while !content.is_empty() {
+            let element = content.next_element_named(entry_name)?;
+
+            let key = <M::Key as XmlDeserializeAttr>::deserialize_from_attribute(element, key_name)
+                .map_err(|err| err.in_attribute(key_name).in_element(entry_name))?;
+            let value =
+                <M::Value as XmlDeserialize>::deserialize_child_nodes(&mut element.content())
+                    .map_err(|err| err.in_element(entry_name))?;
+
+            entries.push((key, value));
+        }
+
+        Ok(M::from_entries(entries))
+    }
+}
+
+impl<M> Clone for MapEntries<'_, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for MapEntries<'_, M> {}
+
+/// Serializes each entry of a map as an element with the key as an attribute.
+impl<M> XmlSerialize for MapEntries<'_, M>
+where
+    M: XmlMap,
+    M::Key: XmlSerializeAttr,
+    M::Value: XmlSerialize,
+{
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        for (key, value) in self.map.entries() {
+            let mut start_tag = BytesStart::new(self.entry_name);
+            key.serialize_as_attribute(&mut start_tag, self.key_name)
+                .map_err(|err| err.in_attribute(self.key_name).in_element(self.entry_name))?;
+
+            writer.write_event(Event::Start(start_tag))?;
+            value
+                .serialize_child_nodes(writer)
+                .map_err(|err| err.in_element(self.entry_name))?;
+            writer.write_event(Event::End(BytesEnd::new(self.entry_name)))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A view of a map which serializes each of its entries as an attribute on an
+/// XML start tag, with the key as the attribute name.
+#[derive(Debug)]
+pub struct MapAttributes<'a, M> {
+    map: &'a M,
+}
+
+impl<'a, M> MapAttributes<'a, M> {
+    /// Creates a view of a map for serializing as attributes.
+    pub fn new(map: &'a M) -> Self {
+        Self { map }
+    }
+}
+
+impl<M> MapAttributes<'_, M>
+where
+    M: XmlMap,
+    M::Key: AsRef<str>,
+    M::Value: XmlSerializeAttr,
+{
+    /// Adds each entry of the map to a start tag as an attribute.
+    pub fn serialize_as_attributes(&self, start_tag: &mut BytesStart) -> Result<(), Error> {
+        for (key, value) in self.map.entries() {
+            value
+                .serialize_as_attribute(start_tag, key.as_ref())
+                .map_err(|err| err.in_attribute(key.as_ref()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<M> MapAttributes<'_, M>
+where
+    M: XmlMap,
+    M::Key: From<String>,
+    M::Value: XmlDeserializeAttr,
+{
+    /// Deserializes a map from the attributes of an element.
+    ///
+    /// Namespace declarations and any attribute named in `excluded`, as for
+    /// [`Element::attribute`], are not included in the map.
+    pub fn deserialize_from_attributes(element: &Element, excluded: &[&str]) -> Result<M, Error> {
+        let mut entries = Vec::new();
+        for (idx, (name, value)) in element.attributes().iter().enumerate() {
+            if name == "xmlns"
+                || name.starts_with("xmlns:")
+                || excluded
+                    .iter()
+                    .any(|excluded| element.attribute_is_named(idx, excluded))
+            {
+                continue;
+            }
+
+            let value = <M::Value as XmlDeserializeAttr>::deserialize_from_attribute_value(value)
+                .map_err(|err| err.in_attribute(name))?;
+            entries.push((M::Key::from(name.clone()), value));
+        }
+
+        Ok(M::from_entries(entries))
+    }
+}
+
+impl<M> Clone for MapAttributes<'_, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for MapAttributes<'_, M> {}
@@ -0,0 +1,353 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! This module provides tracking of the XML namespace declarations in scope
+//! during serialization, so that redundant declarations may be omitted and
+//! prefixes may be chosen for namespaces automatically, along with a type for
+//! naming elements by namespace.
+
+use quick_xml::{events::BytesStart, name::PrefixDeclaration};
+use xml_struct_productions::{is_ncname, XMLNS_NAMESPACE, XML_NAMESPACE};
+
+use crate::Error;
+
+/// The name of an XML element, given as a local name and the name of the
+/// namespace, if any, in which it is found.
+///
+/// Values serialized with [`XmlSerialize::serialize_as_qualified_element`]
+/// are named with a prefix bound to the namespace, declaring one if necessary,
+/// so that the resulting element is always in the given namespace.
+///
+/// [`XmlSerialize::serialize_as_qualified_element`]: crate::XmlSerialize::serialize_as_qualified_element
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QName<'a> {
+    /// The namespace name, or `None` if the element is in no namespace. An
+    /// empty namespace name is treated as no namespace.
+    pub ns: Option<&'a str>,
+
+    /// The local part of the name, which must match the XML `NCName`
+    /// production.
+    pub local: &'a str,
+}
+
+impl<'a> QName<'a> {
+    /// Creates a name for an element in no namespace.
+    pub fn local(local: &'a str) -> Self {
+        Self { ns: None, local }
+    }
+
+    /// Creates a name for an element in the given namespace.
+    pub fn with_ns(ns: &'a str, local: &'a str) -> Self {
+        Self {
+            ns: Some(ns),
+            local,
+        }
+    }
+}
+
+/// The namespace declarations in scope for a writer, along with those to be
+/// made on the start tags of elements named by [`Scope::qualify`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Scope {
+    /// The namespace bindings declared by each open element, from outermost to
+    /// innermost.
+    elements: Vec<Vec<Binding>>,
+
+    /// Declarations awaiting the start tags of the elements they name.
+    pending: Vec<PendingDecl>,
+}
+
+/// A binding of a prefix, or of the default namespace if `None`, to a
+/// namespace name.
+///
+/// An empty namespace name undeclares the default namespace.
+#[derive(Clone, Debug)]
+struct Binding {
+    prefix: Option<String>,
+    name: String,
+}
+
+/// A declaration to be made on start tags with a given name.
+#[derive(Clone, Debug)]
+struct PendingDecl {
+    /// The qualified name of the element on which to make the declaration.
+    element: String,
+
+    /// The number of open elements when the name was qualified, i.e. the depth
+    /// at which the element is to be written.
+    depth: usize,
+
+    binding: Binding,
+}
+
+impl Scope {
+    /// Brings into scope the namespace declarations made on the start tag of a
+    /// newly-opened element.
+    pub(crate) fn open(&mut self, start_tag: &BytesStart) {
+        self.elements.push(declarations_on(start_tag));
+    }
+
+    /// Removes from scope the namespace declarations of the innermost open
+    /// element.
+    pub(crate) fn close(&mut self) {
+        self.elements.pop();
+    }
+
+    /// Gets a copy of a start tag with the declarations pending for it added,
+    /// or `None` if there are none to add.
+    ///
+    /// Declarations made on the start tag itself take precedence over pending
+    /// declarations of the same prefix.
+    pub(crate) fn with_pending_declarations(
+        &self,
+        start_tag: &BytesStart,
+    ) -> Option<BytesStart<'static>> {
+        let additions = self.pending_for(start_tag, &declarations_on(start_tag));
+        if additions.is_empty() {
+            return None;
+        }
+
+        let mut start_tag = start_tag.to_owned();
+        for binding in additions {
+            push_declaration(&mut start_tag, binding.prefix.as_deref(), &binding.name);
+        }
+
+        Some(start_tag)
+    }
+
+    /// Adds to a start tag those of the given namespace declarations which are
+    /// neither already made on it nor in scope with the same binding.
+    pub(crate) fn declare_all<'a, I>(&self, start_tag: &mut BytesStart, declarations: I)
+    where
+        I: IntoIterator<Item = (Option<&'a str>, &'a str)>,
+    {
+        for (prefix, name) in declarations {
+            let is_declared_on_tag = declarations_on(start_tag)
+                .iter()
+                .any(|binding| binding.prefix.as_deref() == prefix);
+
+            if !is_declared_on_tag && self.lookup(prefix) != Some(name) {
+                push_declaration(start_tag, prefix, name);
+            }
+        }
+    }
+
+    /// Ensures that a namespace is bound to a prefix on the element whose start
+    /// tag is given, returning the prefix.
+    pub(crate) fn declare(&self, start_tag: &mut BytesStart, namespace: &str) -> String {
+        if namespace == XML_NAMESPACE {
+            return String::from("xml");
+        }
+
+        let mut on_tag = declarations_on(start_tag);
+        let pending = self.pending_for(start_tag, &on_tag);
+        on_tag.extend(pending.into_iter().cloned());
+
+        let prefix = on_tag
+            .iter()
+            .find(|binding| binding.prefix.is_some() && binding.name == namespace)
+            .and_then(|binding| binding.prefix.clone())
+            .or_else(|| {
+                // A prefix bound in an enclosing scope may be rebound on this
+                // start tag.
+                self.prefix_for(namespace).filter(|prefix| {
+                    !on_tag
+                        .iter()
+                        .any(|binding| binding.prefix.as_ref() == Some(prefix))
+                })
+            });
+
+        prefix.unwrap_or_else(|| {
+            let prefix = self.choose_prefix(&on_tag);
+            push_declaration(start_tag, Some(&prefix), namespace);
+
+            prefix
+        })
+    }
+
+    /// Gets the namespace name to which a prefix, or the default namespace if
+    /// `None`, is bound in the current scope.
+    pub(crate) fn namespace_for(&self, prefix: Option<&str>) -> Option<&str> {
+        self.lookup(prefix).filter(|name| !name.is_empty())
+    }
+
+    /// Gets the name with which to write an element of the given name as a
+    /// child of the innermost open element.
+    ///
+    /// A default namespace in scope is used if it is the element's namespace,
+    /// and otherwise a prefix bound to it. If there is none, an unused prefix
+    /// of the form `ns0`, `ns1`, etc. is chosen. For elements in no namespace,
+    /// any default namespace in scope is undeclared. The declarations this
+    /// requires are left pending for start tags of the returned name, until
+    /// discarded with [`Scope::truncate_pending`].
+    ///
+    /// Names whose local part is not an `NCName`, or which are in the
+    /// namespace reserved for namespace declarations, are rejected.
+    pub(crate) fn qualify(&mut self, name: QName<'_>) -> Result<String, Error> {
+        if !is_ncname(name.local) {
+            return Err(Error::IllFormed(format!(
+                "`{}` is not a valid local name",
+                name.local
+            )));
+        }
+
+        // An empty namespace name can't be bound to a prefix, and undeclares
+        // the default namespace, so it means the same as no namespace.
+        let ns = name.ns.filter(|ns| !ns.is_empty());
+        if ns == Some(XMLNS_NAMESPACE) {
+            return Err(Error::IllFormed(format!(
+                "element `{}` can't be in the namespace `{XMLNS_NAMESPACE}`",
+                name.local
+            )));
+        }
+
+        let (element, binding) = match ns {
+            Some(ns) if self.namespace_for(None) == Some(ns) => (String::from(name.local), None),
+            Some(ns) => match self.prefix_for(ns) {
+                Some(prefix) => (format!("{prefix}:{}", name.local), None),
+                None => {
+                    let prefix = self.choose_prefix(&[]);
+                    let element = format!("{prefix}:{}", name.local);
+                    let binding = Binding {
+                        prefix: Some(prefix),
+                        name: String::from(ns),
+                    };
+
+                    (element, Some(binding))
+                }
+            },
+            None if self.namespace_for(None).is_some() => {
+                let binding = Binding {
+                    prefix: None,
+                    name: String::new(),
+                };
+
+                (String::from(name.local), Some(binding))
+            }
+            None => (String::from(name.local), None),
+        };
+
+        if let Some(binding) = binding {
+            self.pending.push(PendingDecl {
+                element: element.clone(),
+                depth: self.elements.len(),
+                binding,
+            });
+        }
+
+        Ok(element)
+    }
+
+    /// Gets the number of pending declarations.
+    pub(crate) fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Discards all but the given number of pending declarations.
+    pub(crate) fn truncate_pending(&mut self, len: usize) {
+        self.pending.truncate(len);
+    }
+
+    /// Gets the bindings pending for a start tag, other than those of prefixes
+    /// among the given bindings, most recent first.
+    fn pending_for(&self, start_tag: &BytesStart, declared: &[Binding]) -> Vec<&Binding> {
+        let depth = self.elements.len();
+        let element = start_tag.name();
+
+        let mut bindings: Vec<&Binding> = Vec::new();
+        for decl in self.pending.iter().rev() {
+            let prefix = &decl.binding.prefix;
+            let is_shadowed = declared.iter().any(|binding| &binding.prefix == prefix)
+                || bindings.iter().any(|binding| &binding.prefix == prefix);
+
+            if decl.depth == depth && decl.element.as_bytes() == element.as_ref() && !is_shadowed {
+                bindings.push(&decl.binding);
+            }
+        }
+
+        bindings
+    }
+
+    /// Finds the namespace name most recently bound to a prefix, or to the
+    /// default namespace if `None`.
+    fn lookup(&self, prefix: Option<&str>) -> Option<&str> {
+        if prefix == Some("xml") {
+            return Some(XML_NAMESPACE);
+        }
+
+        self.elements
+            .iter()
+            .rev()
+            .flat_map(|bindings| bindings.iter().rev())
+            .find(|binding| binding.prefix.as_deref() == prefix)
+            .map(|binding| binding.name.as_str())
+    }
+
+    /// Finds a prefix bound to a namespace name in the current scope.
+    fn prefix_for(&self, namespace: &str) -> Option<String> {
+        if namespace == XML_NAMESPACE {
+            return Some(String::from("xml"));
+        }
+
+        self.elements
+            .iter()
+            .rev()
+            .flat_map(|bindings| bindings.iter().rev())
+            .filter_map(|binding| {
+                binding
+                    .prefix
+                    .as_deref()
+                    .filter(|_| binding.name == namespace)
+            })
+            // A prefix may be bound to the namespace by an outer element but
+            // rebound by an inner one.
+            .find(|prefix| self.lookup(Some(prefix)) == Some(namespace))
+            .map(String::from)
+    }
+
+    /// Chooses a prefix which is not in scope, awaiting declaration, or among
+    /// the given bindings.
+    fn choose_prefix(&self, declared: &[Binding]) -> String {
+        (0..)
+            .map(|idx| format!("ns{idx}"))
+            .find(|prefix| {
+                let is_used = |binding: &Binding| binding.prefix.as_ref() == Some(prefix);
+
+                self.lookup(Some(prefix)).is_none()
+                    && !self.pending.iter().any(|decl| is_used(&decl.binding))
+                    && !declared.iter().any(is_used)
+            })
+            .expect("there should be an unused prefix")
+    }
+}
+
+/// Gets the namespace declarations made by the attributes of a start tag.
+fn declarations_on(start_tag: &BytesStart) -> Vec<Binding> {
+    let mut attributes = start_tag.attributes();
+    attributes.with_checks(false);
+
+    attributes
+        .flatten()
+        .filter_map(|attribute| {
+            let prefix = match attribute.key.as_namespace_binding()? {
+                PrefixDeclaration::Default => None,
+                PrefixDeclaration::Named(prefix) => {
+                    Some(String::from_utf8_lossy(prefix).into_owned())
+                }
+            };
+
+            let name = attribute.unescape_value().ok()?.into_owned();
+
+            Some(Binding { prefix, name })
+        })
+        .collect()
+}
+
+/// Adds a namespace declaration to a start tag.
+fn push_declaration(start_tag: &mut BytesStart, prefix: Option<&str>, name: &str) {
+    match prefix {
+        Some(prefix) => start_tag.push_attribute((format!("xmlns:{prefix}").as_str(), name)),
+        None => start_tag.push_attribute(("xmlns", name)),
+    }
+}
@@ -0,0 +1,445 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! This module provides a lightweight in-memory representation of XML elements
+//! for use in deserialization.
+//!
+//! Deserializing directly from a stream of `quick_xml` events makes it
+//! difficult to handle flattened fields and enum variants, both of which
+//! require looking ahead at content nodes without consuming them. Instead, we
+//! read a complete element into memory and deserialize from that.
+//!
+//! Namespace prefixes are resolved as each element is read, so that names may
+//! be matched either as written or by namespace name and local name.
+
+use std::io::BufRead;
+
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use xml_struct_productions::XML_NAMESPACE;
+
+use crate::Error;
+
+/// An XML element read from a document, along with its attributes and content
+/// nodes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Element {
+    name: String,
+    namespace: Option<String>,
+    attributes: Vec<(String, String)>,
+
+    /// The namespace name of each attribute, in the same order as
+    /// `attributes`.
+    attribute_namespaces: Vec<Option<String>>,
+
+    children: Vec<Node>,
+}
+
+/// The namespace bindings declared on an element, as pairs of a prefix, or
+/// `None` for the default namespace, and a namespace name.
+///
+/// An empty namespace name undeclares the default namespace.
+type Bindings = Vec<(Option<String>, String)>;
+
+/// A content node of an XML element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    Element(Element),
+
+    /// Unescaped character data, from either a text node or a CDATA section.
+    Text(String),
+}
+
+impl Element {
+    /// Reads the next complete element from an XML document.
+    ///
+    /// Any XML declaration, processing instructions, comments, or text which
+    /// precede the start of the element are skipped.
+    ///
+    /// Text nodes consisting solely of whitespace are discarded from elements
+    /// which also contain child elements, as such whitespace is generally
+    /// indentation rather than meaningful content.
+    ///
+    /// Prefixes which are not bound by a namespace declaration within the
+    /// element are left unresolved; such names can only be matched as written.
+    pub fn read_from<R>(reader: &mut Reader<R>) -> Result<Self, Error>
+    where
+        R: BufRead,
+    {
+        let mut buf = Vec::new();
+
+        // We keep a stack of elements which have been started but not yet
+        // ended. When an element ends, it is added to the content of its
+        // parent, or returned if it has no parent.
+        let mut open_elements: Vec<Element> = Vec::new();
+
+        // The namespace bindings declared by each open element, in the same
+        // order as `open_elements`.
+        let mut scope: Vec<Bindings> = Vec::new();
+
+        loop {
+            let completed = match reader.read_event_into(&mut buf)? {
+                Event::Start(start) => {
+                    let (element, bindings) = Element::from_start_tag(&start, &scope)?;
+                    open_elements.push(element);
+                    scope.push(bindings);
+
+                    None
+                }
+                Event::Empty(start) => Some(Element::from_start_tag(&start, &scope)?.0),
+                Event::End(_) => {
+                    scope.pop();
+
+                    // The end tag may belong to an element enclosing the one
+                    // we were asked to read, e.g. when reading the children of
+                    // an element one at a time.
+                    let Some(mut element) = open_elements.pop() else {
+                        return Err(Error::UnexpectedStructure(String::from(
+                            "end tag found before any element started",
+                        )));
+                    };
+                    element.discard_insignificant_whitespace();
+
+                    Some(element)
+                }
+                Event::Text(text) => {
+                    if let Some(parent) = open_elements.last_mut() {
+                        parent
+                            .children
+                            .push(Node::Text(text.unescape()?.into_owned()));
+                    }
+
+                    None
+                }
+                Event::CData(cdata) => {
+                    if let Some(parent) = open_elements.last_mut() {
+                        let text = reader.decoder().decode(&cdata)?;
+                        parent.children.push(Node::Text(text.into_owned()));
+                    }
+
+                    None
+                }
+                Event::Eof => {
+                    return Err(Error::UnexpectedStructure(String::from(
+                        "document ended before a complete element was read",
+                    )));
+                }
+
+                // Declarations, comments, etc. have no bearing on the content
+                // of the document.
+                _ => None,
+            };
+
+            if let Some(element) = completed {
+                match open_elements.last_mut() {
+                    Some(parent) => parent.children.push(Node::Element(element)),
+                    None => return Ok(element),
+                }
+            }
+
+            buf.clear();
+        }
+    }
+
+    /// The name of the element, including any namespace prefix.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the element without any namespace prefix.
+    pub fn local_name(&self) -> &str {
+        local_name(&self.name)
+    }
+
+    /// The name of the namespace to which the element's prefix, or the default
+    /// namespace if it has none, is bound.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Determines whether the element has the given name.
+    ///
+    /// The name is either a qualified name, matched exactly as written, or a
+    /// namespace name and local name in Clark notation, i.e.
+    /// `{namespace}Name`, matched regardless of the prefix used.
+    pub fn is_named(&self, name: &str) -> bool {
+        name_matches(&self.name, self.namespace.as_deref(), name)
+    }
+
+    /// Gets the unescaped value of the named attribute, if present.
+    ///
+    /// The attribute's name may be given in Clark notation, as for
+    /// [`Element::is_named`]. Attributes without a prefix are in no namespace.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .enumerate()
+            .find(|(idx, _)| self.attribute_is_named(*idx, name))
+            .map(|(_, (_, value))| value.as_str())
+    }
+
+    /// The attributes of the element as name/value pairs, in document order.
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    /// The content nodes of the element, in document order.
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// Creates a cursor over the content nodes of the element.
+    pub fn content(&self) -> Content<'_> {
+        Content {
+            element: self,
+            position: 0,
+        }
+    }
+
+    /// Determines whether the attribute at the given index has the given name,
+    /// as for [`Element::attribute`].
+    pub(crate) fn attribute_is_named(&self, idx: usize, name: &str) -> bool {
+        name_matches(
+            &self.attributes[idx].0,
+            self.attribute_namespaces[idx].as_deref(),
+            name,
+        )
+    }
+
+    /// Creates an element with no content from the name and attributes of a
+    /// start tag, returning it along with the namespace bindings it declares.
+    ///
+    /// `scope` holds the bindings declared by each enclosing element.
+    fn from_start_tag(start: &BytesStart, scope: &[Bindings]) -> Result<(Self, Bindings), Error> {
+        let name = std::str::from_utf8(start.name().as_ref())
+            .map_err(quick_xml::Error::from)?
+            .to_owned();
+
+        let attributes: Vec<(String, String)> = start
+            .attributes()
+            .map(|attr| {
+                let attr = attr.map_err(quick_xml::Error::from)?;
+                let name = std::str::from_utf8(attr.key.as_ref())
+                    .map_err(quick_xml::Error::from)?
+                    .to_owned();
+                let value = attr.unescape_value()?.into_owned();
+
+                Ok((name, value))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        let bindings: Bindings = attributes
+            .iter()
+            .filter_map(|(name, value)| {
+                if name == "xmlns" {
+                    Some((None, value.clone()))
+                } else {
+                    name.strip_prefix("xmlns:")
+                        .map(|prefix| (Some(String::from(prefix)), value.clone()))
+                }
+            })
+            .collect();
+
+        let resolve = |prefix: Option<&str>| -> Option<String> {
+            if prefix == Some("xml") {
+                return Some(String::from(XML_NAMESPACE));
+            }
+
+            std::iter::once(&bindings)
+                .chain(scope.iter().rev())
+                .flatten()
+                .find(|(bound_prefix, _)| bound_prefix.as_deref() == prefix)
+                .map(|(_, namespace)| namespace)
+                .filter(|namespace| !namespace.is_empty())
+                .cloned()
+        };
+
+        let namespace = resolve(prefix(&name));
+
+        // Unprefixed attributes are in no namespace, rather than the default.
+        let attribute_namespaces = attributes
+            .iter()
+            .map(|(name, _)| prefix(name).and_then(|prefix| resolve(Some(prefix))))
+            .collect();
+
+        let element = Self {
+            name,
+            namespace,
+            attributes,
+            attribute_namespaces,
+            children: Vec::new(),
+        };
+
+        Ok((element, bindings))
+    }
+
+    /// Removes whitespace-only text nodes if this element contains child
+    /// elements.
+    fn discard_insignificant_whitespace(&mut self) {
+        let has_child_elements = self
+            .children
+            .iter()
+            .any(|node| matches!(node, Node::Element(_)));
+
+        if has_child_elements {
+            self.children.retain(|node| match node {
+                Node::Text(text) => !text.trim().is_empty(),
+                Node::Element(_) => true,
+            });
+        }
+    }
+}
+
+/// A cursor over the content nodes of an XML element.
+///
+/// Deserialization consumes content nodes in document order, so that multiple
+/// fields (including flattened fields) can share the content of a single
+/// element.
+#[derive(Clone, Debug)]
+pub struct Content<'a> {
+    element: &'a Element,
+    position: usize,
+}
+
+impl<'a> Content<'a> {
+    /// The element whose content nodes are being read.
+    ///
+    /// Attributes of values deserialized from content nodes are read from this
+    /// element.
+    pub fn element(&self) -> &'a Element {
+        self.element
+    }
+
+    /// Whether all content nodes have been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.element.children.len()
+    }
+
+    /// Gets the next content node without consuming it.
+    pub fn peek(&self) -> Option<&'a Node> {
+        self.element.children.get(self.position)
+    }
+
+    /// Consumes the next content node.
+    pub fn next_node(&mut self) -> Option<&'a Node> {
+        let node = self.peek();
+        if node.is_some() {
+            self.position += 1;
+        }
+
+        node
+    }
+
+    /// Consumes the next content node, which must be an element.
+    pub fn next_element(&mut self) -> Result<&'a Element, Error> {
+        match self.peek() {
+            Some(Node::Element(element)) => {
+                self.position += 1;
+
+                Ok(element)
+            }
+            Some(Node::Text(_)) => Err(Error::UnexpectedStructure(format!(
+                "expected element in `{}`, found text",
+                self.element.name
+            ))),
+            None => Err(Error::UnexpectedStructure(format!(
+                "expected element in `{}`, found end of content",
+                self.element.name
+            ))),
+        }
+    }
+
+    /// Consumes the next content node, which must be an element with the given
+    /// name.
+    pub fn next_element_named(&mut self, name: &str) -> Result<&'a Element, Error> {
+        match self.next_element_if_named(name) {
+            Some(element) => Ok(element),
+            None => Err(Error::UnexpectedStructure(format!(
+                "expected element `{name}` in `{}`",
+                self.element.name
+            ))),
+        }
+    }
+
+    /// Consumes the next content node if it is an element with the given name.
+    ///
+    /// The name may be given in Clark notation, as for [`Element::is_named`].
+    pub fn next_element_if_named(&mut self, name: &str) -> Option<&'a Element> {
+        match self.peek() {
+            Some(Node::Element(element)) if element.is_named(name) => {
+                self.position += 1;
+
+                Some(element)
+            }
+            _ => None,
+        }
+    }
+
+    /// Consumes any consecutive text nodes at the current position and returns
+    /// their combined content.
+    ///
+    /// If the next content node is not text, an empty string is returned.
+    pub fn take_text(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(Node::Text(value)) = self.peek() {
+            text.push_str(value);
+            self.position += 1;
+        }
+
+        text
+    }
+
+    /// Checks that no elements remain among the content nodes not yet
+    /// consumed.
+    ///
+    /// Remaining text nodes are not considered.
+    pub fn deny_remaining_elements(&self) -> Result<(), Error> {
+        let remaining = self.element.children[self.position..]
+            .iter()
+            .find_map(|node| match node {
+                Node::Element(element) => Some(element),
+                Node::Text(_) => None,
+            });
+
+        match remaining {
+            Some(element) => Err(Error::UnexpectedStructure(format!(
+                "unexpected element `{}` in `{}`",
+                element.name, self.element.name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The index of the next content node to be consumed.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Gets the prefix of a qualified name, if any.
+fn prefix(name: &str) -> Option<&str> {
+    name.split_once(':').map(|(prefix, _)| prefix)
+}
+
+/// Gets the local part of a qualified name.
+fn local_name(name: &str) -> &str {
+    name.split_once(':')
+        .map_or(name, |(_, local_name)| local_name)
+}
+
+/// Determines whether a node's qualified name and namespace match a name given
+/// either as written or in Clark notation.
+fn name_matches(name: &str, namespace: Option<&str>, candidate: &str) -> bool {
+    match candidate
+        .strip_prefix('{')
+        .and_then(|candidate| candidate.split_once('}'))
+    {
+        Some((candidate_namespace, candidate_local_name)) => {
+            namespace == Some(candidate_namespace) && local_name(name) == candidate_local_name
+        }
+        None => name == candidate,
+    }
+}
@@ -4,8 +4,12 @@
 
 #![cfg(test)]
 
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
 use quick_xml::events::{attributes::Attribute, BytesStart};
-use xml_struct_tests::{serialize_value_as_element, serialize_value_children};
+use xml_struct_tests::{
+    deserialize_value_from_str, serialize_value_as_element, serialize_value_children,
+};
 
 use crate::XmlSerializeAttr;
 
@@ -112,6 +116,27 @@ fn int_as_content_node() {
         "Serializing `u64` should result in bare text content"
     );
 
+    let actual =
+        serialize_value_children(content as i128).expect("Failed to serialize int as text content");
+    assert_eq!(
+        actual, expected,
+        "Serializing `i128` should result in bare text content"
+    );
+
+    let actual =
+        serialize_value_children(content as u128).expect("Failed to serialize int as text content");
+    assert_eq!(
+        actual, expected,
+        "Serializing `u128` should result in bare text content"
+    );
+
+    let actual = serialize_value_children(content as isize)
+        .expect("Failed to serialize int as text content");
+    assert_eq!(
+        actual, expected,
+        "Serializing `isize` should result in bare text content"
+    );
+
     let actual = serialize_value_children(content as usize)
         .expect("Failed to serialize int as text content");
     assert_eq!(
@@ -223,7 +248,9 @@ fn bool_as_attribute_value() {
     let expected = vec![Attribute::from((attr_name, "true"))];
 
     let mut start = BytesStart::new(element_name);
-    content.serialize_as_attribute(&mut start, &attr_name);
+    content
+        .serialize_as_attribute(&mut start, attr_name)
+        .expect("Failed to serialize attribute");
 
     let actual: Vec<_> = start
         .attributes()
@@ -239,7 +266,9 @@ fn bool_as_attribute_value() {
     let expected = vec![Attribute::from((attr_name, "false"))];
 
     let mut start = BytesStart::new(element_name);
-    content.serialize_as_attribute(&mut start, &attr_name);
+    content
+        .serialize_as_attribute(&mut start, attr_name)
+        .expect("Failed to serialize attribute");
 
     let actual: Vec<_> = start
         .attributes()
@@ -276,3 +305,276 @@ fn bool_as_element() {
         "Serializing `bool` should result in bare text content"
     );
 }
+
+#[test]
+fn string_from_element() {
+    let expected = String::from("some <arbitrary> content");
+
+    let actual: String =
+        deserialize_value_from_str("<SomeTag>some &lt;arbitrary&gt;<![CDATA[ content]]></SomeTag>")
+            .expect("Failed to deserialize string");
+    assert_eq!(
+        actual, expected,
+        "Text and CDATA content should be unescaped and combined"
+    );
+
+    let actual: String =
+        deserialize_value_from_str("<SomeTag/>").expect("Failed to deserialize string");
+    assert_eq!(
+        actual, "",
+        "Element without content should produce an empty string"
+    );
+
+    let actual: String =
+        deserialize_value_from_str("<SomeTag>  </SomeTag>").expect("Failed to deserialize string");
+    assert_eq!(
+        actual, "  ",
+        "Whitespace should be preserved in text-only elements"
+    );
+}
+
+#[test]
+fn int_from_element() {
+    let actual: i32 =
+        deserialize_value_from_str("<Int> -17 </Int>").expect("Failed to deserialize int");
+    assert_eq!(
+        actual, -17,
+        "Integers should be parsed with surrounding whitespace ignored"
+    );
+
+    let actual: Result<u8, _> = deserialize_value_from_str("<Int>256</Int>");
+    assert!(
+        actual.is_err(),
+        "Out-of-range integers should fail to parse"
+    );
+}
+
+#[test]
+fn bool_from_element() {
+    for (text, expected) in [("true", true), ("1", true), ("false", false), ("0", false)] {
+        let actual: bool = deserialize_value_from_str(&format!("<Bool>{text}</Bool>"))
+            .expect("Failed to deserialize bool");
+
+        assert_eq!(
+            actual, expected,
+            "`{text}` should be deserialized as `{expected}`"
+        );
+    }
+
+    let actual: Result<bool, _> = deserialize_value_from_str("<Bool>yes</Bool>");
+    assert!(
+        actual.is_err(),
+        "Non-XSD boolean values should fail to parse"
+    );
+}
+
+#[test]
+fn float_as_content_node() {
+    for (content, expected) in [
+        (1.5, "1.5"),
+        (-0.0, "-0"),
+        (1e20, "100000000000000000000"),
+        (1e-7, "0.0000001"),
+        (f64::INFINITY, "INF"),
+        (f64::NEG_INFINITY, "-INF"),
+        (f64::NAN, "NaN"),
+    ] {
+        let actual =
+            serialize_value_children(content).expect("Failed to serialize float as text content");
+        assert_eq!(
+            actual, expected,
+            "Serializing `f64` should result in XML Schema lexical form"
+        );
+
+        let actual = serialize_value_children(content as f32)
+            .expect("Failed to serialize float as text content");
+        assert_eq!(
+            actual, expected,
+            "Serializing `f32` should result in XML Schema lexical form"
+        );
+    }
+}
+
+#[test]
+fn float_with_exponent() {
+    for (content, expected) in [
+        (1e21, "1.0E21"),
+        (-1.5e300, "-1.5E300"),
+        (f64::MAX, "1.7976931348623157E308"),
+        (1e-8, "1.0E-8"),
+        (f64::MIN_POSITIVE, "2.2250738585072014E-308"),
+        (5e-324, "5.0E-324"),
+    ] {
+        let actual =
+            serialize_value_children(content).expect("Failed to serialize float as text content");
+        assert_eq!(
+            actual, expected,
+            "Very large and small `f64` values should be written with an exponent"
+        );
+
+        let actual: f64 = deserialize_value_from_str(&format!("<Float>{actual}</Float>"))
+            .expect("Failed to deserialize float");
+        assert_eq!(actual, content, "Floats with an exponent should round-trip");
+    }
+
+    for (content, expected) in [
+        (f32::MAX, "3.4028235E38"),
+        (f32::MIN_POSITIVE, "1.1754944E-38"),
+        (1e-45f32, "1.0E-45"),
+    ] {
+        let actual =
+            serialize_value_children(content).expect("Failed to serialize float as text content");
+        assert_eq!(
+            actual, expected,
+            "Very large and small `f32` values should be written with an exponent"
+        );
+
+        let actual: f32 = deserialize_value_from_str(&format!("<Float>{actual}</Float>"))
+            .expect("Failed to deserialize float");
+        assert_eq!(actual, content, "Floats with an exponent should round-trip");
+    }
+}
+
+#[test]
+fn float_as_attribute_value() {
+    let mut start = BytesStart::new("foo");
+    f64::NEG_INFINITY
+        .serialize_as_attribute(&mut start, "bar")
+        .expect("Failed to serialize attribute");
+    0.25f32
+        .serialize_as_attribute(&mut start, "baz")
+        .expect("Failed to serialize attribute");
+
+    let actual: Vec<_> = start
+        .attributes()
+        .map(|result| result.expect("Failed to get attribute value"))
+        .collect();
+
+    assert_eq!(
+        actual,
+        vec![
+            Attribute::from(("bar", "-INF")),
+            Attribute::from(("baz", "0.25"))
+        ],
+        "Floats should be serialized as attribute values in XML Schema lexical form"
+    );
+}
+
+#[test]
+fn float_from_element() {
+    for (text, expected) in [
+        ("1.5", 1.5),
+        (" -2E3 ", -2000.0),
+        ("INF", f64::INFINITY),
+        ("+INF", f64::INFINITY),
+        ("-INF", f64::NEG_INFINITY),
+    ] {
+        let actual: f64 = deserialize_value_from_str(&format!("<Float>{text}</Float>"))
+            .expect("Failed to deserialize float");
+
+        assert_eq!(
+            actual, expected,
+            "`{text}` should be deserialized as `{expected}`"
+        );
+    }
+
+    let actual: f32 =
+        deserialize_value_from_str("<Float>NaN</Float>").expect("Failed to deserialize float");
+    assert!(actual.is_nan(), "`NaN` should be deserialized as NaN");
+
+    for text in ["inf", "infinity", "nan", "abc"] {
+        let actual: Result<f64, _> = deserialize_value_from_str(&format!("<Float>{text}</Float>"));
+        assert!(
+            actual.is_err(),
+            "Non-XSD float value `{text}` should fail to parse"
+        );
+    }
+}
+
+#[test]
+fn char_as_content_node() {
+    let actual = serialize_value_children('<').expect("Failed to serialize char as text content");
+    assert_eq!(
+        actual, "&lt;",
+        "Serializing `char` should result in escaped text content"
+    );
+
+    let actual: char =
+        deserialize_value_from_str("<Char> </Char>").expect("Failed to deserialize char");
+    assert_eq!(actual, ' ', "Whitespace should be significant for `char`");
+
+    let actual: Result<char, _> = deserialize_value_from_str("<Char>ab</Char>");
+    assert!(
+        actual.is_err(),
+        "Text of more than one character should fail to parse as `char`"
+    );
+}
+
+#[test]
+fn sequences_as_content_nodes() {
+    let expected = "abc";
+
+    let actual = serialize_value_children(["a", "b", "c"])
+        .expect("Failed to serialize array as content nodes");
+    assert_eq!(
+        actual, expected,
+        "Serializing an array should serialize each element in order"
+    );
+
+    let values = vec!["a", "b", "c"];
+    let actual = serialize_value_children(values.as_slice())
+        .expect("Failed to serialize slice as content nodes");
+    assert_eq!(
+        actual, expected,
+        "Serializing a slice should serialize each element in order"
+    );
+
+    let actual = serialize_value_children(VecDeque::from(["a", "b", "c"]))
+        .expect("Failed to serialize `VecDeque` as content nodes");
+    assert_eq!(
+        actual, expected,
+        "Serializing a `VecDeque` should serialize each element from front to back"
+    );
+
+    let actual = serialize_value_children(BTreeSet::from(["c", "a", "b"]))
+        .expect("Failed to serialize `BTreeSet` as content nodes");
+    assert_eq!(
+        actual, expected,
+        "Serializing a `BTreeSet` should serialize each element in ascending order"
+    );
+
+    let actual = serialize_value_children(HashSet::from(["a"]))
+        .expect("Failed to serialize `HashSet` as content nodes");
+    assert_eq!(
+        actual, "a",
+        "Serializing a `HashSet` should serialize each element"
+    );
+}
+
+#[test]
+fn tuple_as_content_nodes() {
+    let actual = serialize_value_as_element((String::from("a"), 1, true), "Tuple")
+        .expect("Failed to serialize tuple as element");
+    assert_eq!(
+        actual, "<Tuple>a1true</Tuple>",
+        "Serializing a tuple should serialize each member in order"
+    );
+
+    let actual = serialize_value_children((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12))
+        .expect("Failed to serialize tuple as content nodes");
+    assert_eq!(
+        actual, "123456789101112",
+        "Serializing a 12-tuple should serialize each member in order"
+    );
+}
+
+#[test]
+fn tuple_from_element() {
+    let actual: (String,) = deserialize_value_from_str("<Tuple>some text</Tuple>")
+        .expect("Failed to deserialize tuple");
+    assert_eq!(
+        actual,
+        (String::from("some text"),),
+        "Deserializing a tuple should deserialize each member in order"
+    );
+}
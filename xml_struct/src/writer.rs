@@ -0,0 +1,278 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! This module provides the writer to which values are serialized, which
+//! tracks the namespace declarations in scope in the document being written.
+
+use quick_xml::events::{BytesStart, Event};
+use xml_struct_productions::{is_name, is_xml_char};
+
+use crate::{namespaces::Scope, Error, QName};
+
+/// A writer for an XML document, wrapping a `quick_xml` writer.
+///
+/// The writer tracks the namespace declarations made on the start tags written
+/// to it, so that declarations already in scope are not repeated and prefixes
+/// may be chosen for namespaces automatically. Each writer tracks only its own
+/// document, so a separate document may be written to another writer in the
+/// course of writing this one.
+///
+/// # Interoperability with `quick_xml`
+///
+/// A writer may be created from a configured `quick_xml::Writer` with
+/// [`From`], e.g. `XmlWriter::from(quick_xml::Writer::new(output))`, and the
+/// underlying output is available through [`XmlWriter::get_ref`],
+/// [`XmlWriter::get_mut`], and [`XmlWriter::into_inner`]. Events should be
+/// written through [`XmlWriter::write_event`] rather than directly to the
+/// output, so that the namespace declarations they make are tracked.
+///
+/// # Strict mode
+///
+/// By default, names and text are written as given, even where they can't
+/// appear in well-formed XML. A writer created with [`XmlWriter::strict`]
+/// instead checks that element and attribute names match the XML `Name`
+/// production, and that text, attribute values, CDATA sections, and other
+/// character data consist only of characters matching the XML `Char`
+/// production. An event which fails these checks is not written, and
+/// [`Error::IllFormed`] is returned in its place. Events written before it are
+/// not affected.
+#[derive(Clone)]
+pub struct XmlWriter<W> {
+    inner: quick_xml::Writer<W>,
+    scope: Scope,
+    is_strict: bool,
+}
+
+impl<W> XmlWriter<W>
+where
+    W: std::io::Write,
+{
+    /// Creates a writer for a new document.
+    pub fn new(inner: W) -> Self {
+        quick_xml::Writer::new(inner).into()
+    }
+
+    /// Creates a writer for a new document, indenting each element by the
+    /// given number of the given character per level of nesting.
+    pub fn new_with_indent(inner: W, indent_char: u8, indent_size: usize) -> Self {
+        quick_xml::Writer::new_with_indent(inner, indent_char, indent_size).into()
+    }
+
+    /// Enables strict mode, in which events which would not be well-formed are
+    /// rejected rather than written.
+    pub fn strict(mut self) -> Self {
+        self.is_strict = true;
+
+        self
+    }
+
+    /// Determines whether the writer is in strict mode.
+    pub fn is_strict(&self) -> bool {
+        self.is_strict
+    }
+
+    /// Writes an event to the document.
+    ///
+    /// Namespace declarations made on start tags are in scope until the
+    /// corresponding end tag is written. In strict mode, the event is first
+    /// checked for names and characters which can't appear in well-formed XML.
+    pub fn write_event<'a, E>(&mut self, event: E) -> Result<(), Error>
+    where
+        E: AsRef<Event<'a>>,
+    {
+        match event.as_ref() {
+            Event::Start(start_tag) => {
+                let start_tag = self.with_pending_declarations(start_tag);
+                self.write_inner(Event::Start(start_tag.borrow()))?;
+                self.scope.open(&start_tag);
+            }
+            Event::Empty(start_tag) => {
+                let start_tag = self.with_pending_declarations(start_tag);
+                self.write_inner(Event::Empty(start_tag.borrow()))?;
+            }
+            Event::End(end_tag) => {
+                self.write_inner(Event::End(end_tag.borrow()))?;
+                self.scope.close();
+            }
+            event => self.write_inner(event.borrow())?,
+        }
+
+        Ok(())
+    }
+
+    /// Adds to a start tag those of the given namespace declarations which are
+    /// neither already made on it nor in scope with the same binding.
+    ///
+    /// Each declaration is a prefix, or `None` for the default namespace, and
+    /// the namespace name to which it is bound. Where a prefix is declared more
+    /// than once, only the first declaration is made.
+    pub fn declare_namespaces<'a, I>(&self, start_tag: &mut BytesStart, declarations: I)
+    where
+        I: IntoIterator<Item = (Option<&'a str>, &'a str)>,
+    {
+        self.scope.declare_all(start_tag, declarations);
+    }
+
+    /// Ensures that a namespace is bound to a prefix on the element whose start
+    /// tag is given, returning the prefix.
+    ///
+    /// A prefix already bound to the namespace on the start tag or in the
+    /// current scope is reused. Otherwise, an unused prefix of the form `ns0`,
+    /// `ns1`, etc. is chosen and declared on the start tag.
+    ///
+    /// Attributes can't carry their own declarations, so the namespace of an
+    /// attribute should be declared on its element's start tag in this way
+    /// before naming the attribute.
+    pub fn declare_namespace(&self, start_tag: &mut BytesStart, namespace: &str) -> String {
+        self.scope.declare(start_tag, namespace)
+    }
+
+    /// Gets the namespace name to which a prefix, or the default namespace if
+    /// `None`, is bound in the current scope.
+    pub fn namespace_for(&self, prefix: Option<&str>) -> Option<&str> {
+        self.scope.namespace_for(prefix)
+    }
+
+    /// Calls a function to write an element named in a namespace, giving it
+    /// the qualified name with which to write the element.
+    ///
+    /// A default namespace in scope is used if it is the element's namespace,
+    /// and otherwise a prefix bound to it. If there is none, an unused prefix
+    /// is chosen and declared on each start tag of the element written by the
+    /// function. For elements in no namespace, any default namespace in scope
+    /// is undeclared on the element in the same way.
+    pub fn with_element_name<F>(&mut self, name: QName<'_>, write: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Self, &str) -> Result<(), Error>,
+    {
+        let pending_len = self.scope.pending_len();
+
+        let result = self
+            .scope
+            .qualify(name)
+            .and_then(|element_name| write(self, &element_name));
+
+        // The declarations are needed only for the element named here, so
+        // they must not apply to any written later.
+        self.scope.truncate_pending(pending_len);
+
+        result
+    }
+
+    /// Gets a reference to the underlying output.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying output.
+    ///
+    /// Anything written directly to the output is not seen by the writer, so
+    /// namespace declarations written in this way are not tracked.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Consumes the writer, returning the underlying output.
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner()
+    }
+
+    /// Writes an event to the underlying writer, checking it first if in strict
+    /// mode.
+    fn write_inner(&mut self, event: Event) -> Result<(), Error> {
+        if self.is_strict {
+            check_event(&event)?;
+        }
+
+        self.inner.write_event(event)?;
+
+        Ok(())
+    }
+
+    /// Gets a start tag with any pending namespace declarations for it added.
+    fn with_pending_declarations<'b>(&self, start_tag: &'b BytesStart) -> BytesStart<'b> {
+        self.scope
+            .with_pending_declarations(start_tag)
+            .unwrap_or_else(|| start_tag.borrow())
+    }
+}
+
+impl<W> From<quick_xml::Writer<W>> for XmlWriter<W> {
+    fn from(inner: quick_xml::Writer<W>) -> Self {
+        Self {
+            inner,
+            scope: Scope::default(),
+            is_strict: false,
+        }
+    }
+}
+
+/// Checks that the names and character data of an event can appear in
+/// well-formed XML.
+fn check_event(event: &Event) -> Result<(), Error> {
+    match event {
+        Event::Start(tag) | Event::Empty(tag) => {
+            check_name(as_str(tag.name().as_ref())?)?;
+
+            let mut attributes = tag.attributes();
+
+            // Duplicate attributes aren't a matter of illegal names or
+            // characters, so we leave them to the writer's consumer.
+            attributes.with_checks(false);
+
+            for attribute in attributes {
+                let attribute = attribute.map_err(ill_formed)?;
+
+                check_name(as_str(attribute.key.as_ref())?)?;
+                check_chars(&attribute.unescape_value().map_err(ill_formed)?)?;
+            }
+        }
+        Event::End(tag) => check_name(as_str(tag.name().as_ref())?)?,
+        Event::Text(text) => check_chars(&text.unescape().map_err(ill_formed)?)?,
+        Event::CData(text) => check_chars(as_str(text)?)?,
+        Event::Comment(text) => check_chars(as_str(text)?)?,
+        Event::Decl(decl) => check_chars(as_str(decl)?)?,
+        Event::PI(text) => check_chars(as_str(text)?)?,
+        Event::DocType(text) => check_chars(as_str(text)?)?,
+        Event::Eof => (),
+    }
+
+    Ok(())
+}
+
+/// Creates an error describing an event which couldn't be interpreted.
+fn ill_formed<E>(err: E) -> Error
+where
+    E: std::fmt::Display,
+{
+    Error::IllFormed(format!("event could not be read: {err}"))
+}
+
+/// Interprets the content of an event as UTF-8 text.
+fn as_str(bytes: &[u8]) -> Result<&str, Error> {
+    std::str::from_utf8(bytes).map_err(ill_formed)
+}
+
+/// Checks that a name matches the XML `Name` production.
+fn check_name(name: &str) -> Result<(), Error> {
+    if is_name(name) {
+        Ok(())
+    } else {
+        Err(Error::IllFormed(format!(
+            "`{name}` is not a valid XML name"
+        )))
+    }
+}
+
+/// Checks that text consists only of characters matching the XML `Char`
+/// production.
+fn check_chars(text: &str) -> Result<(), Error> {
+    match text.chars().find(|c| !is_xml_char(*c)) {
+        Some(c) => Err(Error::IllFormed(format!(
+            "character U+{:04X} is not allowed in XML",
+            u32::from(c)
+        ))),
+        None => Ok(()),
+    }
+}
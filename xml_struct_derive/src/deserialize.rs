@@ -0,0 +1,132 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+mod codegen;
+
+use proc_macro2::{Ident, TokenStream};
+use quote::ToTokens as _;
+use syn::{DataEnum, DataStruct, Generics};
+
+use crate::{
+    input::{
+        process_enum_variant, process_named_field, process_unnamed_field, validate_fields,
+        validate_variants,
+    },
+    TypeProps,
+};
+
+use self::codegen::{
+    generate_deserialize_impl_for, with_enum_variants, with_struct_fields, with_text_variants,
+};
+
+/// Generates an implementation of the `XmlDeserialize` trait for a Rust struct
+/// and its fields.
+pub(crate) fn write_deserialize_impl_for_struct(
+    ident: Ident,
+    generics: Generics,
+    input: DataStruct,
+    props: TypeProps,
+) -> TokenStream {
+    // We build a list of errors so that we can combine them later and emit
+    // them all instead of quitting at the first we encounter.
+    let mut errors = Vec::new();
+
+    // Process the struct's fields in order to determine how to represent them,
+    // based on struct type and any consumer-applied attributes. Fields are
+    // bound to local variables during deserialization rather than accessed, so
+    // we don't need any particular accessor expression.
+    let (fields, kind) = match input.fields {
+        syn::Fields::Named(fields) => (
+            fields
+                .named
+                .into_iter()
+                .map(process_named_field(
+                    &mut errors,
+                    &props,
+                    Ident::to_token_stream,
+                ))
+                .collect(),
+            codegen::ConstructorKind::Struct,
+        ),
+        syn::Fields::Unnamed(fields) => (
+            fields
+                .unnamed
+                .into_iter()
+                .enumerate()
+                .map(process_unnamed_field(&mut errors, |_| {
+                    TokenStream::default()
+                }))
+                .collect(),
+            codegen::ConstructorKind::Tuple,
+        ),
+        syn::Fields::Unit => (vec![], codegen::ConstructorKind::Unit),
+    };
+
+    validate_fields(&mut errors, &props, &fields);
+
+    // Combine and return errors if there are any. If none, we've successfully
+    // handled all fields and can generate the final implementation.
+    let err = errors.into_iter().reduce(|mut acc, err| {
+        acc.combine(err);
+
+        acc
+    });
+
+    if let Some(err) = err {
+        return err.into_compile_error();
+    }
+
+    generate_deserialize_impl_for(
+        ident,
+        generics,
+        with_struct_fields(fields, kind, props.should_deny_unknown_elements),
+    )
+}
+
+/// Generates an implementation of the `XmlDeserialize` trait (and the
+/// `XmlDeserializeAttr` trait if appropriate) for a Rust enum, its variants,
+/// and their fields.
+pub(crate) fn write_deserialize_impl_for_enum(
+    ident: Ident,
+    generics: Generics,
+    input: DataEnum,
+    props: TypeProps,
+) -> TokenStream {
+    // We build a list of errors so that we can combine them later and emit
+    // them all instead of quitting at the first we encounter.
+    let mut errors = Vec::new();
+
+    let variants: Vec<_> = input
+        .variants
+        .into_iter()
+        .map(process_enum_variant(&mut errors, &props))
+        .collect();
+
+    validate_variants(&mut errors, &props, &variants);
+
+    let err = errors.into_iter().reduce(|mut acc, err| {
+        acc.combine(err);
+
+        acc
+    });
+
+    if let Some(err) = err {
+        return err.into_compile_error();
+    }
+
+    if props.should_serialize_as_text {
+        // We depend on the code which generates `TypeProps` to handle verifying
+        // that this enum consists solely of unit variants when setting this
+        // property.
+        return generate_deserialize_impl_for(ident, generics, with_text_variants(variants));
+    }
+
+    let type_name = ident.to_string();
+
+    generate_deserialize_impl_for(
+        ident,
+        generics,
+        with_enum_variants(type_name, variants, props.should_deny_unknown_elements),
+    )
+}
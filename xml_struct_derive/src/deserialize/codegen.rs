@@ -0,0 +1,470 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::Generics;
+
+use crate::{
+    input::{Field, FieldKind, Variant, VariantKind},
+    names::{field_error_context_tokens, field_name_to_match_tokens, variant_name_to_match_tokens},
+    FieldRepr, MapRepr, DEFAULT_MAP_ENTRY_NAME, DEFAULT_MAP_KEY_NAME,
+};
+
+/// Generates an implementation of the `XmlDeserialize` trait and, if
+/// appropriate, the `XmlDeserializeAttr` trait.
+pub(super) fn generate_deserialize_impl_for(
+    type_ident: Ident,
+    generics: Generics,
+    ImplTokenSets {
+        from_element_impl,
+        child_nodes_body,
+        from_attr_body,
+    }: ImplTokenSets,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attr_impl = if let Some(body) = from_attr_body {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::xml_struct::XmlDeserializeAttr for #type_ident #ty_generics #where_clause {
+                fn deserialize_from_attribute_value(value: &str) -> Result<Self, ::xml_struct::Error> {
+                    #body
+                }
+            }
+        }
+    } else {
+        // In cases where there is no clear text representation of a value, we
+        // provide no derivation of `XmlDeserializeAttr`.
+        TokenStream::default()
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::xml_struct::XmlDeserialize for #type_ident #ty_generics #where_clause {
+            #from_element_impl
+
+            fn deserialize_child_nodes(
+                content: &mut ::xml_struct::Content<'_>
+            ) -> Result<Self, ::xml_struct::Error> {
+                #child_nodes_body
+            }
+        }
+
+        #attr_impl
+    }
+}
+
+/// The sets of tokens which make up the bodies of `XmlDeserialize` and
+/// `XmlDeserializeAttr` trait methods.
+pub(super) struct ImplTokenSets {
+    /// The implementation of `XmlDeserialize::deserialize_from_element()` if
+    /// it is necessary to override the provided default implementation.
+    from_element_impl: TokenStream,
+
+    /// The body of `XmlDeserialize::deserialize_child_nodes()`.
+    child_nodes_body: TokenStream,
+
+    /// The body of `XmlDeserializeAttr::deserialize_from_attribute_value()` if
+    /// the type is capable of being deserialized as such.
+    from_attr_body: Option<TokenStream>,
+}
+
+/// The syntactic form used to construct a struct or enum variant from its
+/// fields.
+pub(super) enum ConstructorKind {
+    Struct,
+    Tuple,
+    Unit,
+}
+
+/// Creates the sets of tokens necessary to deserialize a struct with the
+/// provided fields.
+///
+/// If `deny_remaining_elements` is set, it is an error for child elements to
+/// remain once the fields have been read from the struct's own element.
+pub(super) fn with_struct_fields(
+    fields: Vec<Field>,
+    kind: ConstructorKind,
+    deny_remaining_elements: bool,
+) -> ImplTokenSets {
+    let body = generate_fields_body(quote!(Self), kind, fields, None, false);
+
+    // The content nodes of a flattened struct are followed by those of the
+    // enclosing struct's remaining fields, so remaining elements can only be
+    // checked for once the struct's own element has been read.
+    let from_element_impl = if deny_remaining_elements {
+        quote! {
+            fn deserialize_from_element(
+                element: &::xml_struct::Element
+            ) -> Result<Self, ::xml_struct::Error> {
+                let mut content = element.content();
+                let value = <Self as ::xml_struct::XmlDeserialize>::deserialize_child_nodes(&mut content)?;
+                content.deny_remaining_elements()?;
+
+                Ok(value)
+            }
+        }
+    } else {
+        TokenStream::default()
+    };
+
+    ImplTokenSets {
+        from_element_impl,
+        child_nodes_body: quote! {
+            #body
+        },
+
+        // There is no clear text representation of an arbitrary struct, so we
+        // cannot provide an `XmlDeserializeAttr` derivation.
+        from_attr_body: None,
+    }
+}
+
+/// Creates the sets of tokens necessary to deserialize a unit-only enum from
+/// text nodes or attribute values.
+pub(super) fn with_text_variants(variants: Vec<Variant>) -> ImplTokenSets {
+    let value_from_text: TokenStream = variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            let text = Literal::string(&variant.xml_name);
+
+            quote! {
+                if candidate == #text {
+                    return Ok(Self::#ident);
+                }
+            }
+        })
+        .collect();
+
+    // Values are matched exactly as written first, so that values with
+    // leading or trailing whitespace round-trip, and then with surrounding
+    // whitespace such as indentation removed.
+    let value_from_text = quote! {
+        for candidate in [text, text.trim()] {
+            #value_from_text
+        }
+
+        Err(::xml_struct::Error::UnexpectedStructure(format!(
+            "unrecognized value `{}`", text
+        )))
+    };
+
+    ImplTokenSets {
+        // Text enums are read from text alone, so the default implementation
+        // of `deserialize_from_element()` is sufficient.
+        from_element_impl: TokenStream::default(),
+        child_nodes_body: quote! {
+            let text = content.take_text();
+            let text = text.as_str();
+
+            #value_from_text
+        },
+        from_attr_body: Some(quote! {
+            let text = value;
+
+            #value_from_text
+        }),
+    }
+}
+
+/// Creates the sets of tokens necessary to deserialize an enum with the
+/// provided variants.
+///
+/// If `deny_remaining_elements` is set, it is an error for child elements to
+/// remain once a variant's fields have been read from its element.
+pub(super) fn with_enum_variants(
+    type_name: String,
+    variants: Vec<Variant>,
+    deny_remaining_elements: bool,
+) -> ImplTokenSets {
+    let variant_checks: TokenStream = variants
+        .into_iter()
+        .map(|variant| {
+            let name_tokens = variant_name_to_match_tokens(&variant);
+            let ident = variant.ident;
+
+            let (kind, fields) = match variant.kind {
+                VariantKind::Struct(fields) => (ConstructorKind::Struct, fields),
+                VariantKind::Tuple(fields) => (ConstructorKind::Tuple, fields),
+                VariantKind::Unit => (ConstructorKind::Unit, vec![]),
+            };
+
+            let body = if fields.is_empty() {
+                generate_fields_body(quote!(Self::#ident), kind, fields, None, false)
+            } else {
+                let fields_body = generate_fields_body(
+                    quote!(Self::#ident),
+                    kind,
+                    fields,
+                    Some(&name_tokens),
+                    deny_remaining_elements,
+                );
+
+                // The fields of the variant are represented within the
+                // variant's element rather than in the enclosing content.
+                quote! {
+                    let content = &mut element.content();
+
+                    #fields_body
+                }
+            };
+
+            quote! {
+                if element.is_named(#name_tokens) {
+                    return { #body };
+                }
+            }
+        })
+        .collect();
+
+    let type_name = Literal::string(&type_name);
+
+    ImplTokenSets {
+        // Remaining elements are checked for within each variant's element,
+        // so the default implementation of `deserialize_from_element()` is
+        // sufficient.
+        from_element_impl: TokenStream::default(),
+        child_nodes_body: quote! {
+            let element = content.next_element()?;
+
+            #variant_checks
+
+            Err(::xml_struct::Error::UnexpectedStructure(format!(
+                "unrecognized variant `{}` of `{}`", element.name(), #type_name
+            )))
+        },
+
+        // There is no clear text representation of an arbitrary enum variant,
+        // so we cannot provide an `XmlDeserializeAttr` derivation.
+        from_attr_body: None,
+    }
+}
+
+/// Generates calls to deserialize each of the provided fields from `content`,
+/// followed by an expression constructing a value from them.
+///
+/// Fields are deserialized in declaration order, matching the order in which
+/// they are serialized. If the fields are read from within an element handled
+/// by the generated code itself, `element_name` is its name, used in
+/// describing the location of any error. If `deny_remaining_elements` is also
+/// set, it is an error for child elements of that element to remain once the
+/// fields have been read.
+fn generate_fields_body(
+    constructor: TokenStream,
+    kind: ConstructorKind,
+    fields: Vec<Field>,
+    element_name: Option<&TokenStream>,
+    deny_remaining_elements: bool,
+) -> TokenStream {
+    // Fields are bound to local variables by position so that field names
+    // can't collide with the names of variables in generated code.
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|idx| format_ident!("__field{idx}"))
+        .collect();
+
+    let element_path = element_name.map(|name| quote!(.in_element(#name)));
+
+    let field_calls: TokenStream = fields
+        .iter()
+        .zip(&bindings)
+        .map(|(field, binding)| {
+            let ty = &field.ty;
+            let hook = &field.props.deserialize_with;
+            let item_name = &field.props.item_name;
+
+            // Skipped fields aren't represented in XML, so we use the default
+            // value of the field's type.
+            if field.props.should_skip {
+                return quote! {
+                    let #binding: #ty = <#ty as ::core::default::Default>::default();
+                };
+            }
+
+            // Each call is paired with any path segments to add to an error
+            // it returns, beyond those added by the callee.
+            let (call, path) = match (&field.kind, field.props.repr) {
+                // A map flattened into attributes takes all attributes not
+                // claimed by other fields.
+                (_, FieldRepr::Attribute)
+                    if matches!(field.props.map_repr, Some(MapRepr::Attributes)) =>
+                {
+                    let excluded = fields
+                        .iter()
+                        .filter(|other| {
+                            !other.props.should_skip
+                                && matches!(other.props.repr, FieldRepr::Attribute)
+                                && other.props.map_repr.is_none()
+                        })
+                        .map(field_name_to_match_tokens);
+
+                    (
+                        quote! {
+                            ::xml_struct::MapAttributes::<#ty>::deserialize_from_attributes(content.element(), &[#(#excluded),*])
+                        },
+                        TokenStream::default(),
+                    )
+                }
+
+                (_, FieldRepr::Attribute) if field.props.list_separator.is_some() => {
+                    let name = field_name_to_match_tokens(field);
+                    let separator = field.props.list_separator.as_deref();
+
+                    (
+                        quote! {
+                            ::xml_struct::AttrList::deserialize_from_attribute(content.element(), #name, #separator)
+                        },
+                        quote!(.in_attribute(#name)),
+                    )
+                }
+
+                (_, FieldRepr::Attribute) => {
+                    let name = field_name_to_match_tokens(field);
+                    let function = match hook {
+                        Some(hook) => hook.path_for("deserialize_from_attribute"),
+                        None => quote!(<#ty as ::xml_struct::XmlDeserializeAttr>::deserialize_from_attribute),
+                    };
+
+                    (
+                        quote!(#function(content.element(), #name)),
+                        quote!(.in_attribute(#name)),
+                    )
+                }
+
+                // Entries of a map are read from elements with a common name,
+                // either within an element named for the field or directly
+                // from the content nodes.
+                (kind, _) if matches!(field.props.map_repr, Some(MapRepr::Entries)) => {
+                    let entry_name = item_name.as_deref().unwrap_or(DEFAULT_MAP_ENTRY_NAME);
+                    let key_name = field
+                        .props
+                        .map_key_name
+                        .as_deref()
+                        .unwrap_or(DEFAULT_MAP_KEY_NAME);
+
+                    let call = if matches!(kind, FieldKind::Named { .. }) && !field.props.should_flatten {
+                        let child_name = field_name_to_match_tokens(field);
+
+                        quote! {
+                            ::xml_struct::MapEntries::<#ty>::deserialize_as_element(content, #child_name, #entry_name, #key_name)
+                        }
+                    } else {
+                        quote! {
+                            ::xml_struct::MapEntries::<#ty>::deserialize_child_nodes(content, #entry_name, #key_name)
+                        }
+                    };
+
+                    (call, TokenStream::default())
+                }
+
+                // Entries of a repeated field are read from each consecutive
+                // element named for the field.
+                (FieldKind::Named { .. }, FieldRepr::Element) if field.props.should_repeat => {
+                    let child_name = field_name_to_match_tokens(field);
+
+                    (
+                        quote! {
+                            ::core::iter::from_fn(|| content.next_element_if_named(#child_name))
+                                .map(|element| {
+                                    ::xml_struct::XmlDeserialize::deserialize_from_element(element)
+                                        .map_err(|err| err.in_element(#child_name))
+                                })
+                                .collect::<Result<#ty, ::xml_struct::Error>>()
+                        },
+                        TokenStream::default(),
+                    )
+                }
+
+                (FieldKind::Named { .. }, FieldRepr::Element) if !field.props.should_flatten => {
+                    let child_name = field_name_to_match_tokens(field);
+
+                    match (hook, item_name) {
+                        (_, Some(item_name)) => (
+                            quote! {
+                                ::xml_struct::Items::deserialize_as_element(content, #child_name, #item_name)
+                            },
+                            TokenStream::default(),
+                        ),
+
+                        // Implementations of `deserialize_as_element()` name
+                        // their own element in any error, but we can't rely on
+                        // hooks to do the same.
+                        (Some(hook), None) => {
+                            let function = hook.path_for("deserialize_as_element");
+
+                            (
+                                quote!(#function(content, #child_name)),
+                                quote!(.in_element(#child_name)),
+                            )
+                        }
+                        (None, None) => (
+                            quote! {
+                                <#ty as ::xml_struct::XmlDeserialize>::deserialize_as_element(content, #child_name)
+                            },
+                            TokenStream::default(),
+                        ),
+                    }
+                }
+
+                // If this is a tuple struct or the consumer has specifically
+                // requested a flat or text representation, deserialize
+                // without a containing element.
+                _ => {
+                    let call = match (hook, item_name) {
+                        (_, Some(item_name)) => quote! {
+                            ::xml_struct::Items::deserialize_child_nodes(content, #item_name)
+                        },
+                        (Some(hook), None) => {
+                            let function = hook.path_for("deserialize_child_nodes");
+
+                            quote!(#function(content))
+                        }
+                        (None, None) => quote! {
+                            <#ty as ::xml_struct::XmlDeserialize>::deserialize_child_nodes(content)
+                        },
+                    };
+
+                    (call, TokenStream::default())
+                }
+            };
+
+            let annotate = field_error_context_tokens(field, quote!(#path #element_path));
+
+            quote! {
+                let #binding: #ty = #call.map_err(#annotate)?;
+            }
+        })
+        .collect();
+
+    let construction = match kind {
+        ConstructorKind::Struct => {
+            let idents = fields.iter().map(|field| match &field.kind {
+                FieldKind::Named { ident, .. } => ident,
+                FieldKind::Unnamed { .. } => panic!("unnamed field in struct constructor"),
+            });
+
+            quote!(#constructor { #(#idents: #bindings),* })
+        }
+        ConstructorKind::Tuple => quote!(#constructor(#(#bindings),*)),
+        ConstructorKind::Unit => constructor,
+    };
+
+    let remaining_check = match element_name {
+        Some(name) if deny_remaining_elements => quote! {
+            content
+                .deny_remaining_elements()
+                .map_err(|err| err.in_element(#name))?;
+        },
+        _ => TokenStream::default(),
+    };
+
+    quote! {
+        #field_calls
+
+        #remaining_check
+
+        Ok(#construction)
+    }
+}
@@ -0,0 +1,424 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Processing of derive macro input into representations of fields and enum
+//! variants for use in code generation.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, ToTokens};
+use syn::spanned::Spanned as _;
+
+use crate::{
+    validate_text_content_fields, FieldProps, FieldRepr, MapRepr, NamespaceDecl, TypeProps,
+    VariantProps,
+};
+
+/// Creates a callback for processing a `syn` enum variant into codegen details.
+///
+/// Unless the variant is explicitly renamed, names of the variant and its
+/// fields are derived according to the properties of the enclosing type.
+pub(crate) fn process_enum_variant<'cb>(
+    errors: &'cb mut Vec<syn::Error>,
+    type_props: &'cb TypeProps,
+) -> impl FnMut(syn::Variant) -> Variant + 'cb {
+    move |variant| {
+        let props =
+            VariantProps::try_from_attrs(&variant.attrs, type_props.should_serialize_as_text)
+                .unwrap_or_else(|err| {
+                    errors.push(err);
+
+                    VariantProps::default()
+                });
+
+        // Process the variants's fields in order to determine how to represent
+        // them, based on variant type and any consumer-applied attributes.
+        let kind = match variant.fields {
+            syn::Fields::Named(fields) => {
+                let fields: Vec<_> = fields
+                    .named
+                    .into_iter()
+                    .map(process_named_field(
+                        errors,
+                        type_props,
+                        Ident::to_token_stream,
+                    ))
+                    .collect();
+                validate_fields(errors, type_props, &fields);
+
+                VariantKind::Struct(fields)
+            }
+            syn::Fields::Unnamed(fields) => {
+                let fields: Vec<_> = fields
+                    .unnamed
+                    .into_iter()
+                    .enumerate()
+                    .map(process_unnamed_field(errors, |idx| {
+                        format_ident!("field{idx}").into_token_stream()
+                    }))
+                    .collect();
+                validate_fields(errors, type_props, &fields);
+
+                VariantKind::Tuple(fields)
+            }
+            syn::Fields::Unit => VariantKind::Unit,
+        };
+
+        let xml_name = match &props.rename {
+            Some(name) => name.clone(),
+            None => type_props.rename_all.apply_to_variant(&variant.ident),
+        };
+
+        // A namespace given for the variant takes precedence over those given
+        // for the type, and an explicit prefix for variants takes precedence
+        // over the type's namespace.
+        let (ns_prefix, ns_name) = match (props.namespace_prefix, props.ns_name) {
+            (Some(prefix), _) => (Some(prefix), None),
+            (None, Some(ns_name)) => (None, Some(ns_name)),
+            (None, None) => match &type_props.ns_prefix_for_variants {
+                Some(prefix) => (Some(prefix.clone()), None),
+                None => (None, type_props.ns_name.clone()),
+            },
+        };
+
+        Variant {
+            ident: variant.ident,
+            xml_name,
+            ns_prefix,
+            ns_name,
+            default_ns_name: props.default_ns_name,
+            ns_decls: props.ns_decls,
+            kind,
+        }
+    }
+}
+
+/// Creates a callback for extracting representation details from a named field
+/// (i.e., a field of a regular struct or a struct enum variant) and its
+/// attributes.
+///
+/// The `accessor_generator` callback should, based on the name of a field,
+/// return an expression evaluating to a reference to the value of that field
+/// (either on `self` or within a match arm).
+///
+/// The XML name and representation of the field are derived from the
+/// properties of the enclosing type unless specified by its attributes.
+pub(crate) fn process_named_field<'cb, 'g: 'cb, G>(
+    errors: &'cb mut Vec<syn::Error>,
+    type_props: &'cb TypeProps,
+    mut accessor_generator: G,
+) -> impl FnMut(syn::Field) -> Field + 'cb
+where
+    G: FnMut(&Ident) -> TokenStream + 'g,
+{
+    move |field| {
+        let span = field.span();
+
+        // We should be able to unwrap without panicking, since we know this is
+        // a named field.
+        let ident = field.ident.unwrap();
+        let accessor = accessor_generator(&ident);
+
+        let mut props =
+            FieldProps::try_from_attrs(field.attrs, true, type_props.default_field_repr)
+                .unwrap_or_else(collect_field_processing_error(errors));
+
+        // Fields represented as elements are named in the enclosing type's
+        // namespace unless they specify their own namespace or prefix.
+        let is_element = matches!(props.repr, FieldRepr::Element) && !props.should_flatten;
+        if is_element && props.ns_name.is_none() && props.namespace_prefix.is_none() {
+            props.ns_name = type_props.ns_name.clone();
+        }
+
+        let xml_name = match &props.rename {
+            Some(name) => name.clone(),
+            None => type_props.rename_all.apply_to_field(&ident),
+        };
+
+        Field {
+            kind: FieldKind::Named { ident, xml_name },
+            ty: field.ty.into_token_stream(),
+            accessor,
+            props,
+            span,
+        }
+    }
+}
+
+/// Creates a callback for extracting representation details from an unnamed
+/// field (i.e., a field of a tuple struct or a tuple enum variant) and its
+/// attributes.
+///
+/// The `accessor_generator` callback should, based on the position of a field,
+/// return an expression evaluating to a reference to the value of that field
+/// (either on `self` or within a match arm).
+pub(crate) fn process_unnamed_field<'cb, 'g: 'cb, G>(
+    errors: &'cb mut Vec<syn::Error>,
+    mut accessor_generator: G,
+) -> impl FnMut((usize, syn::Field)) -> Field + 'cb
+where
+    G: FnMut(usize) -> TokenStream + 'g,
+{
+    move |(idx, field)| {
+        let span = field.span();
+        let accessor = accessor_generator(idx);
+
+        let props = FieldProps::try_from_attrs(field.attrs, false, FieldRepr::Element)
+            .unwrap_or_else(collect_field_processing_error(errors));
+
+        Field {
+            kind: FieldKind::Unnamed { idx },
+            ty: field.ty.into_token_stream(),
+            accessor,
+            props,
+            span,
+        }
+    }
+}
+
+/// Creates a callback for handling errors in processing field properties.
+fn collect_field_processing_error(
+    errors: &mut Vec<syn::Error>,
+) -> impl FnMut(syn::Error) -> FieldProps + '_ {
+    |err| {
+        errors.push(err);
+
+        FieldProps::default()
+    }
+}
+
+/// Verifies that the combination of processed fields in a struct or enum
+/// variant can be represented in XML.
+pub(crate) fn validate_fields(
+    errors: &mut Vec<syn::Error>,
+    type_props: &TypeProps,
+    fields: &[Field],
+) {
+    let props = fields.iter().map(|field| (&field.props, field.span));
+
+    if let Err(err) = validate_text_content_fields(props) {
+        errors.push(err);
+    }
+
+    // Two attributes with the same name on one element are not well-formed, so
+    // colliding attribute names are always an error.
+    validate_unique_names(
+        errors,
+        fields.iter().filter(|field| {
+            matches!(field.props.repr, FieldRepr::Attribute)
+                && !matches!(field.props.map_repr, Some(MapRepr::Attributes))
+        }),
+        "attribute",
+    );
+
+    // Sibling elements with the same name are well-formed, but can't be told
+    // apart when deserializing, so consumers may opt in to treating them as
+    // an error.
+    if type_props.should_deny_duplicate_elements {
+        validate_unique_names(
+            errors,
+            fields.iter().filter(|field| {
+                matches!(field.props.repr, FieldRepr::Element) && !field.props.should_flatten
+            }),
+            "element",
+        );
+    }
+}
+
+/// Verifies that no two of the given fields share an XML name once any
+/// namespace prefix or name is applied.
+///
+/// Namespaces are compared as written, so fields which specify the same
+/// namespace through different expressions are not detected.
+fn validate_unique_names<'a>(
+    errors: &mut Vec<syn::Error>,
+    fields: impl Iterator<Item = &'a Field>,
+    node_kind: &str,
+) {
+    let mut seen_names = Vec::new();
+    for field in fields.filter(|field| !field.props.should_skip) {
+        let (ident, xml_name) = match &field.kind {
+            FieldKind::Named { ident, xml_name } => (ident, xml_name),
+
+            // Unnamed fields are never represented by named nodes.
+            FieldKind::Unnamed { .. } => continue,
+        };
+
+        let prefix = field.props.namespace_prefix.as_ref();
+        let ns_name = field.props.ns_name.as_ref();
+        let name = comparable_name(prefix, ns_name, xml_name);
+
+        if seen_names.contains(&name) {
+            errors.push(syn::Error::new(
+                ident.span(),
+                format!(
+                    "duplicate {node_kind} name `{}`",
+                    display_name(prefix, ns_name, xml_name)
+                ),
+            ));
+        } else {
+            seen_names.push(name);
+        }
+    }
+}
+
+/// Verifies that no two variants of an enum share an XML name once any
+/// namespace prefix or name is applied or, for text enums, a text value.
+///
+/// Such variants can't be told apart when deserializing. As for fields,
+/// namespaces are compared as written.
+pub(crate) fn validate_variants(
+    errors: &mut Vec<syn::Error>,
+    type_props: &TypeProps,
+    variants: &[Variant],
+) {
+    let description = if type_props.should_serialize_as_text {
+        "text value"
+    } else {
+        "variant name"
+    };
+
+    let mut seen_names = Vec::new();
+    for variant in variants {
+        let prefix = variant.ns_prefix.as_ref();
+        let ns_name = variant.ns_name.as_ref();
+        let name = comparable_name(prefix, ns_name, &variant.xml_name);
+
+        if seen_names.contains(&name) {
+            errors.push(syn::Error::new(
+                variant.ident.span(),
+                format!(
+                    "duplicate {description} `{}`",
+                    display_name(prefix, ns_name, &variant.xml_name)
+                ),
+            ));
+        } else {
+            seen_names.push(name);
+        }
+    }
+}
+
+/// Gets a representation of an XML name and any namespace prefix or name
+/// which may be compared with others, with namespaces as written.
+fn comparable_name(
+    prefix: Option<&TokenStream>,
+    ns_name: Option<&TokenStream>,
+    xml_name: &str,
+) -> (Option<String>, Option<String>, String) {
+    (
+        prefix.map(ToString::to_string),
+        ns_name.map(ToString::to_string),
+        xml_name.to_owned(),
+    )
+}
+
+/// Formats an XML name for use in an error message.
+///
+/// Namespaces given as literals are included in the name for clarity.
+fn display_name(
+    prefix: Option<&TokenStream>,
+    ns_name: Option<&TokenStream>,
+    xml_name: &str,
+) -> String {
+    let literal_value = |tokens: Option<&TokenStream>| {
+        tokens
+            .and_then(|tokens| syn::parse2::<syn::LitStr>(tokens.clone()).ok())
+            .map(|literal| literal.value())
+    };
+
+    match (literal_value(prefix), literal_value(ns_name)) {
+        (Some(prefix), _) => format!("{prefix}:{xml_name}"),
+        (None, Some(ns_name)) => format!("{{{ns_name}}}{xml_name}"),
+        (None, None) => xml_name.to_owned(),
+    }
+}
+
+/// Divides the fields of a struct or enum variant into those which will be
+/// represented as attributes and those which will be represented as child nodes.
+///
+/// Fields which are always skipped in serialization are omitted from both.
+pub(crate) fn partition_fields(fields: Vec<Field>) -> Fields {
+    let (attr_fields, child_fields) = fields
+        .into_iter()
+        .filter(|field| !field.props.should_skip)
+        .partition(|field| matches!(field.props.repr, FieldRepr::Attribute));
+
+    Fields {
+        attr_fields,
+        child_fields,
+    }
+}
+
+#[derive(Debug)]
+/// A representation of an enum variant.
+pub(crate) struct Variant {
+    // The identifier for the variant.
+    pub ident: Ident,
+
+    // The name of the XML element or text value representing the variant.
+    pub xml_name: String,
+
+    // Any namespace prefix to apply to the variant's element.
+    pub ns_prefix: Option<TokenStream>,
+
+    // The name of any namespace in which the variant's element is named.
+    pub ns_name: Option<TokenStream>,
+
+    // Any declaration of a default namespace on the variant's element.
+    pub default_ns_name: Option<TokenStream>,
+
+    // Declarations of namespaces on the variant's element.
+    pub ns_decls: Vec<NamespaceDecl>,
+
+    // The form of the variant, along with any fields.
+    pub kind: VariantKind,
+}
+
+#[derive(Debug)]
+/// The form of an enum variant and its contained fields.
+pub(crate) enum VariantKind {
+    Struct(Vec<Field>),
+    Tuple(Vec<Field>),
+    Unit,
+}
+
+#[derive(Debug)]
+/// A representation of a struct or enum field.
+pub(crate) struct Field {
+    // The form of the field, along with any identifier.
+    pub kind: FieldKind,
+
+    // The type of the field.
+    pub ty: TokenStream,
+
+    // An expression evaluating to a reference to the value of the field.
+    pub accessor: TokenStream,
+
+    // Properties affecting the serialization of the field.
+    pub props: FieldProps,
+
+    // The span of the field's declaration, for use in error reporting.
+    pub span: Span,
+}
+
+#[derive(Debug)]
+/// A container for partitioned attribute and child element fields.
+pub(crate) struct Fields {
+    pub attr_fields: Vec<Field>,
+    pub child_fields: Vec<Field>,
+}
+
+#[derive(Debug)]
+/// The form of a field, whether named or unnamed.
+pub(crate) enum FieldKind {
+    Named {
+        ident: Ident,
+
+        // The name of the XML element or attribute representing the field.
+        xml_name: String,
+    },
+    Unnamed {
+        // The position of the field within its struct or variant.
+        idx: usize,
+    },
+}
@@ -2,6 +2,9 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+mod deserialize;
+mod input;
+mod names;
 mod properties;
 mod serialize;
 
@@ -9,7 +12,10 @@ use syn::{parse_macro_input, DeriveInput};
 
 pub(crate) use properties::*;
 
-use crate::serialize::{write_serialize_impl_for_enum, write_serialize_impl_for_struct};
+use crate::{
+    deserialize::{write_deserialize_impl_for_enum, write_deserialize_impl_for_struct},
+    serialize::{write_serialize_impl_for_enum, write_serialize_impl_for_struct},
+};
 
 // This value must match the `attributes` attribute for the derive macro.
 const MACRO_ATTRIBUTE: &str = "xml_struct";
@@ -134,8 +140,12 @@ const MACRO_ATTRIBUTE: &str = "xml_struct";
 ///   <Element xmlns="http://foo.example/"/>
 ///   ```
 ///
-///   **NOTE**: The namespace will not be specified if values are serialized as
-///   content nodes only.
+///   **NOTE**: If values are serialized as content nodes only, e.g. as a
+///   flattened field, the namespace is instead declared on the enclosing
+///   element by a derived implementation; see
+///   `XmlSerialize::declared_namespaces()`. It will not be specified if it is
+///   already declared on an enclosing element written to the same
+///   `xml_struct::XmlWriter`.
 ///
 /// - `ns = ("foo", "http://foo.example/")`
 ///
@@ -148,8 +158,30 @@ const MACRO_ATTRIBUTE: &str = "xml_struct";
 ///
 ///   Multiple namespaces may be declared for each structure.
 ///
-///   **NOTE**: The namespace will not be specified if values are serialized as
-///   content nodes only.
+///   Prefixes and names given as string literals are checked at compile time:
+///   each prefix must be a valid XML name without colons and may be declared
+///   only once per structure, `xmlns` may not be declared, and `xml` may only be
+///   bound to `http://www.w3.org/XML/1998/namespace`. The same holds for names
+///   given with `default_ns`, `variant_ns_prefix`, and `ns_prefix` where
+///   applicable, and namespace names may not be empty.
+///
+///   **NOTE**: If values are serialized as content nodes only, e.g. as a
+///   flattened field, the namespace is instead declared on the enclosing
+///   element by a derived implementation; see
+///   `XmlSerialize::declared_namespaces()`. It will not be specified if it is
+///   already declared on an enclosing element written to the same
+///   `xml_struct::XmlWriter`.
+///
+/// - `ns = "http://foo.example/"`
+///
+///   Specifies that the named element fields of the marked structure and the
+///   variants of the marked enum should be named in the given namespace, as if
+///   each field were marked with the `ns` field option below. Fields with a
+///   namespace prefix or namespace of their own are unaffected, as are
+///   variants if `variant_ns_prefix` is specified.
+///
+///   Only one namespace name may be given for each structure, and it may be
+///   combined with the tuple form above.
 ///
 /// - `text`
 ///
@@ -171,6 +203,91 @@ const MACRO_ATTRIBUTE: &str = "xml_struct";
 ///   **NOTE**: This option is only valid for enums which are not serialized as
 ///   text nodes.
 ///
+/// - `fields = "attribute"`
+///
+///   Specifies the type of XML structure which named fields of the marked
+///   structure (or of the marked enum's variants) represent when not otherwise
+///   specified. Supported values are `"element"` (the default) and
+///   `"attribute"`.
+///
+///   Individual fields may override this with the `element` or `attribute`
+///   options. Flattened and unnamed fields are always serialized as content
+///   nodes.
+///
+/// - `rename_all = "camelCase"`
+///
+///   Specifies the case convention used to derive XML names from the
+///   identifiers of the marked structure's fields or the marked enum's variants
+///   and their fields. Text enum values are derived in the same way.
+///
+///   Supported values are `"PascalCase"` (the default), `"camelCase"`,
+///   `"snake_case"`, `"kebab-case"`, `"lowercase"`, and `"UPPERCASE"`. Field
+///   identifiers are assumed to be in snake_case and variant identifiers in
+///   PascalCase, as is conventional in Rust.
+///
+/// - `deny_duplicate_elements`
+///
+///   Specifies that it is an error for two fields of the marked structure (or of
+///   one of the marked enum's variants) to be serialized as elements with the
+///   same name, as such elements can't be told apart when deserializing.
+///
+///   **NOTE**: Two fields serialized as attributes with the same name are
+///   always an error, as the resulting XML would not be well-formed.
+///
+/// - `deny_unknown_elements`
+///
+///   Specifies that, when deserializing, it is an error for child elements to
+///   remain once all fields have been read from the element representing the
+///   marked structure (or one of the marked enum's variants), e.g. an element
+///   not matching any field or a second element for a field which is not
+///   `repeated`. By default, such elements are ignored.
+///
+///   This is not checked when the marked structure is read as a flattened
+///   field, as the content nodes of the enclosing element's other fields follow
+///   its own.
+///
+/// ## Enum Variants
+///
+/// These options affect the serialization of a single enum variant.
+///
+/// - `rename = "SomeName"`
+///
+///   Specifies the name to use for the element representing the marked variant,
+///   in place of one derived from its identifier. For text enums, this is the
+///   text or attribute value representing the variant and may be any string,
+///   e.g. `"1.0"`.
+///
+/// - `default_ns = "http://foo.example/"`
+///
+/// - `ns = ("foo", "http://foo.example/")`
+///
+///   Declares a namespace on the element representing the marked variant, as
+///   with the options of the same name for data structures. Namespaces declared
+///   for the enum are declared on each variant's element as well, except where
+///   the variant declares the same prefix or its own default namespace.
+///
+/// - `ns_prefix = "foo"`
+///
+///   Specifies that the element representing the marked variant should include
+///   a namespace prefix, in place of any given with `variant_ns_prefix`, i.e.
+///
+///   ```text
+///   <foo:Variant/>
+///   ```
+///
+/// - `ns = "http://foo.example/"`
+///
+///   Specifies that the element representing the marked variant should be
+///   named in the given namespace, as with the option of the same name for
+///   fields. This takes the place of any namespace or prefix given for the
+///   enum's variants, and may not be combined with `ns_prefix`.
+///
+/// **NOTE**: Namespace options are not valid for variants of text enums.
+///
+/// **NOTE**: Two variants of one enum represented by elements with the same
+/// name, or by the same text value, are always an error, as they can't be told
+/// apart when deserializing.
+///
 /// ## Structure Fields
 ///
 /// These options affect the serialization of a single field in a struct or enum
@@ -184,13 +301,126 @@ const MACRO_ATTRIBUTE: &str = "xml_struct";
 /// - `element`
 ///
 ///   Specifies that the marked field should be serialized as an XML element.
-///   This is the default behavior, and use of this attribute is optional.
+///   This is the default behavior unless the containing type specifies
+///   `fields = "attribute"`.
 ///
 /// - `flatten`
 ///
 ///   Specifies that the marked field should be serialized as content nodes
 ///   without an enclosing XML element.
 ///
+///   Namespaces declared by the field's type are declared on the element
+///   representing the containing structure, as are those of unnamed fields.
+///   Where the element or another such field declares the same prefix, the
+///   first declaration is kept. Likewise, attributes of the field's type are
+///   set on that element, and are read from it when deserializing.
+///
+/// - `text`
+///
+///   Specifies that the marked field should be serialized as the text content
+///   of the element representing the containing structure, allowing elements
+///   with both attributes and text, i.e.
+///
+///   ```text
+///   <Body BodyType="HTML">some text</Body>
+///   ```
+///
+///   **NOTE**: A structure or enum variant containing a text field may not
+///   contain any other fields serialized as content nodes, though it may
+///   contain attribute fields.
+///
+/// - `cdata`
+///
+///   Specifies that the text of the marked field should be serialized as CDATA
+///   sections rather than as escaped text, i.e.
+///
+///   ```text
+///   <Field><![CDATA[<p>some text</p>]]></Field>
+///   ```
+///
+///   The field's type must implement `AsRef<str>`; see `xml_struct::Cdata`.
+///   This option does not affect deserialization, as CDATA sections and text
+///   are read identically.
+///
+///   **NOTE**: This option is not valid for attribute fields.
+///
+/// - `item = "Item"`
+///
+///   Specifies that each entry of the marked collection field should be
+///   serialized as an XML element with the given name, i.e.
+///
+///   ```text
+///   <Field>
+///       <Item>...</Item>
+///       <Item>...</Item>
+///   </Field>
+///   ```
+///
+///   The field's type must dereference to a slice for serialization and
+///   implement `FromIterator` for deserialization; see `xml_struct::Items`.
+///
+///   **NOTE**: This option is not valid for attribute fields.
+///
+/// - `list` or `list = ","`
+///
+///   Specifies that the entries of the marked collection field should be
+///   serialized as a single attribute value, separated by a space (as in an
+///   XML Schema `xs:list`) or by the given separator, i.e.
+///   `Field="first second"`.
+///
+///   Each entry's type must implement `XmlSerializeAttr` and
+///   `XmlDeserializeAttr`, and the field's type must dereference to a slice for
+///   serialization and implement `FromIterator` for deserialization; see
+///   `xml_struct::AttrList`.
+///
+///   **NOTE**: This option is only valid for attribute fields, and is not valid
+///   in combination with `map` or custom serialization functions.
+///
+/// - `repeated`
+///
+///   Specifies that each entry of the marked collection field should be
+///   serialized as an XML element named for the field, without an enclosing
+///   element, i.e.
+///
+///   ```text
+///   <Field>...</Field>
+///   <Field>...</Field>
+///   ```
+///
+///   A reference to the field's type must implement `IntoIterator` for
+///   serialization, and the type must implement `FromIterator` for
+///   deserialization.
+///
+///   **NOTE**: This option is only valid for named fields serialized as
+///   elements, and is not valid in combination with `item`, `cdata`, or custom
+///   serialization functions.
+///
+/// - `map = "elements"`, `map = "entries"`, or `map = "attributes"`
+///
+///   Specifies how the entries of the marked map field should be represented.
+///   With `"elements"`, the default for `BTreeMap` and `HashMap`, each value is
+///   an element named by its key. With `"entries"`, each entry is an element
+///   with a common name and the key as an attribute, i.e.
+///
+///   ```text
+///   <Field>
+///       <Entry Key="first">...</Entry>
+///       <Entry Key="second">...</Entry>
+///   </Field>
+///   ```
+///
+///   The entry name may be set with `item` and the key attribute name with
+///   `key = "Name"`. With `"attributes"`, each value is an attribute on the
+///   parent element named by its key, and attributes not claimed by other
+///   fields are collected into the map during deserialization.
+///
+///   The field's type must implement `xml_struct::XmlMap`; see
+///   `xml_struct::MapEntries` and `xml_struct::MapAttributes`.
+///
+///   **NOTE**: This option is not valid in combination with `repeated`,
+///   `cdata`, or custom serialization functions. `"attributes"` is only valid
+///   for named fields which are not flattened or serialized as elements.
+///
 /// - `ns_prefix = "foo"`
 ///
 ///   Specifies that the marked field, when serialized as an XML element or
@@ -200,6 +430,72 @@ const MACRO_ATTRIBUTE: &str = "xml_struct";
 ///   ```text
 ///   <foo:Field/>
 ///   ```
+///
+/// - `ns = "http://foo.example/"`
+///
+///   Specifies that the marked field, when serialized as an XML element or
+///   attribute, should be named in the given namespace. A prefix already bound
+///   to the namespace by an enclosing element is reused; otherwise, a prefix of
+///   the form `ns0` is chosen and declared on the element containing the
+///   field, i.e.
+///
+///   ```text
+///   <Element xmlns:ns0="http://foo.example/" ns0:Field="value">
+///       <ns0:Other/>
+///   </Element>
+///   ```
+///
+///   When deserializing, such names are matched by namespace regardless of the
+///   prefix used.
+///
+///   **NOTE**: This option is only valid for named fields which are not
+///   flattened or serialized as text, and may not be combined with
+///   `ns_prefix`.
+///
+/// - `rename = "SomeName"`
+///
+///   Specifies the name to use for the marked field when serialized as an XML
+///   element or attribute, in place of one derived from its identifier. Any
+///   namespace prefix is added to this name.
+///
+///   **NOTE**: This option is only valid for named fields.
+///
+/// - `skip`
+///
+///   Specifies that the marked field should never be serialized. When
+///   deserializing, the field is populated with its type's `Default` value.
+///
+/// - `skip_serializing_if = "path::to::function"`
+///
+///   Specifies a function which determines whether the marked field should be
+///   omitted from serialization. The function must be callable as
+///   `fn(&T) -> bool`, where `T` is the type of the field, and the field is
+///   omitted when it returns `true`, e.g. `"Option::is_none"`. This option does
+///   not affect deserialization.
+///
+/// - `serialize_with = "path::to::function"`
+///
+///   Specifies a function to call in place of the field type's `XmlSerialize`
+///   or `XmlSerializeAttr` implementation. The function takes a reference to
+///   the value of the field followed by the remaining arguments of the trait
+///   method it replaces, i.e. `serialize_as_attribute()` for attribute fields,
+///   `serialize_child_nodes()` for flattened or unnamed fields, and
+///   `serialize_as_element()` otherwise.
+///
+/// - `deserialize_with = "path::to::function"`
+///
+///   Specifies a function to call in place of the field type's `XmlDeserialize`
+///   or `XmlDeserializeAttr` implementation. The function takes the arguments
+///   of the trait method it replaces, i.e. `deserialize_from_attribute()`,
+///   `deserialize_child_nodes()`, or `deserialize_as_element()`, chosen as for
+///   `serialize_with`.
+///
+/// - `with = "path::to::module"`
+///
+///   Equivalent to specifying both `serialize_with` and `deserialize_with` with
+///   functions of the given module named for the trait methods they replace,
+///   e.g. `module::serialize_as_element` and `module::deserialize_as_element`.
+///   Only the functions used for the field's representation need be defined.
 #[proc_macro_derive(XmlSerialize, attributes(xml_struct))]
 pub fn derive_xml_serialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -223,3 +519,41 @@ pub fn derive_xml_serialize(input: proc_macro::TokenStream) -> proc_macro::Token
     // at the last minute.
     .into()
 }
+
+/// A macro providing automated derivation of the `XmlDeserialize` trait.
+///
+/// The resulting implementation accepts XML of the same form as that produced
+/// by a derived `XmlSerialize` implementation for the same type, and the same
+/// `xml_struct` options are honored. See the documentation of `XmlSerialize`'s
+/// derive macro for details.
+///
+/// Fields are deserialized in declaration order. Namespace declarations made
+/// with `default_ns` or `ns` are not checked, while prefixes specified with
+/// `ns_prefix` or `variant_ns_prefix` must match those in the XML exactly.
+/// Names in a namespace given with the `ns = "http://foo.example/"` form are
+/// matched by namespace and local name, regardless of prefix.
+///
+/// Enums which consist solely of unit variants and are marked `text` will also
+/// receive an implementation of the `XmlDeserializeAttr` trait.
+#[proc_macro_derive(XmlDeserialize, attributes(xml_struct))]
+pub fn derive_xml_deserialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let props = match TypeProps::try_from_input(&input) {
+        Ok(props) => props,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
+    let DeriveInput {
+        generics, ident, ..
+    } = input;
+
+    match input.data {
+        syn::Data::Struct(input) => {
+            write_deserialize_impl_for_struct(ident, generics, input, props)
+        }
+        syn::Data::Enum(input) => write_deserialize_impl_for_enum(ident, generics, input, props),
+        syn::Data::Union(_) => panic!("Deserializing unions from XML is unsupported"),
+    }
+    .into()
+}
@@ -0,0 +1,298 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Conversion of Rust identifiers to XML names.
+
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt as _;
+
+use crate::{
+    input::{Field, FieldKind, Variant},
+    RenameRule,
+};
+
+/// Converts the name of a field to a string suitable for use as a tag name.
+///
+/// This will generate code for concatenating the field's XML name with any
+/// namespace prefix to be added. Fields named in a namespace are instead named
+/// by `field_qualified_name_tokens()` or `attribute_name_to_string_tokens()`.
+pub(crate) fn field_name_to_string_tokens(field: &Field) -> TokenStream {
+    match &field.kind {
+        FieldKind::Named { xml_name, .. } => match &field.props.ns_name {
+            Some(_) => panic!("cannot stringify field named in a namespace"),
+            None => name_to_string_tokens(xml_name, field.props.namespace_prefix.as_ref()),
+        },
+
+        FieldKind::Unnamed { .. } => panic!("cannot stringify unnamed field"),
+    }
+}
+
+/// Generates an `xml_struct::QName` naming the element for a field, if the
+/// field is named in a namespace.
+pub(crate) fn field_qualified_name_tokens(field: &Field) -> Option<TokenStream> {
+    match &field.kind {
+        FieldKind::Named { xml_name, .. } => field
+            .props
+            .ns_name
+            .as_ref()
+            .map(|ns_name| qualified_name_tokens(xml_name, ns_name)),
+
+        FieldKind::Unnamed { .. } => None,
+    }
+}
+
+/// Converts the name of an attribute field to a string.
+///
+/// If the field is named in a namespace, this will generate code for
+/// qualifying the name with a prefix bound to that namespace on the start tag
+/// `start_tag`, declaring one if necessary.
+pub(crate) fn attribute_name_to_string_tokens(field: &Field) -> TokenStream {
+    match (&field.kind, &field.props.ns_name) {
+        (FieldKind::Named { xml_name, .. }, Some(ns_name)) => quote! {
+            &::std::format!(
+                "{}:{}",
+                writer.declare_namespace(&mut start_tag, #ns_name),
+                #xml_name,
+            )
+        },
+
+        _ => field_name_to_string_tokens(field),
+    }
+}
+
+/// Converts the name of a field to a string against which to match names when
+/// deserializing.
+///
+/// Names in a namespace are given in Clark notation, i.e. `{namespace}Name`,
+/// so that they are matched regardless of the prefix used in the document.
+pub(crate) fn field_name_to_match_tokens(field: &Field) -> TokenStream {
+    match &field.kind {
+        FieldKind::Named { xml_name, .. } => match &field.props.ns_name {
+            Some(ns_name) => clark_name_tokens(xml_name, ns_name),
+            None => name_to_string_tokens(xml_name, field.props.namespace_prefix.as_ref()),
+        },
+
+        FieldKind::Unnamed { .. } => panic!("cannot stringify unnamed field"),
+    }
+}
+
+/// Generates a closure which annotates an `xml_struct::Error` with the field
+/// in which it occurred.
+///
+/// `path` is a chain of calls adding segments to the error's path, e.g.
+/// `.in_attribute("Id")`, made before the field is recorded. The field is
+/// described by its name as written in Rust, or its position if unnamed, and
+/// the name of the enclosing type.
+pub(crate) fn field_error_context_tokens(field: &Field, path: TokenStream) -> TokenStream {
+    let field_name = match &field.kind {
+        FieldKind::Named { ident, .. } => Literal::string(&ident.unraw().to_string()),
+        FieldKind::Unnamed { idx } => Literal::string(&idx.to_string()),
+    };
+
+    quote! {
+        |err: ::xml_struct::Error| {
+            err #path .in_field(::core::any::type_name::<Self>(), #field_name)
+        }
+    }
+}
+
+/// Converts the name of an enum variant to a string suitable for use as a tag
+/// name.
+///
+/// As with fields, this will also generate code for concatenating the variant
+/// name with any namespace prefix to be added. Variants named in a namespace
+/// are instead named by `variant_qualified_name_tokens()`.
+pub(crate) fn variant_name_to_string_tokens(variant: &Variant) -> TokenStream {
+    match &variant.ns_name {
+        Some(_) => panic!("cannot stringify variant named in a namespace"),
+        None => name_to_string_tokens(&variant.xml_name, variant.ns_prefix.as_ref()),
+    }
+}
+
+/// Generates an `xml_struct::QName` naming the element for an enum variant, if
+/// the variant is named in a namespace.
+pub(crate) fn variant_qualified_name_tokens(variant: &Variant) -> Option<TokenStream> {
+    variant
+        .ns_name
+        .as_ref()
+        .map(|ns_name| qualified_name_tokens(&variant.xml_name, ns_name))
+}
+
+/// Converts the name of an enum variant to a string against which to match
+/// names when deserializing, as for fields.
+pub(crate) fn variant_name_to_match_tokens(variant: &Variant) -> TokenStream {
+    match &variant.ns_name {
+        Some(ns_name) => clark_name_tokens(&variant.xml_name, ns_name),
+        None => name_to_string_tokens(&variant.xml_name, variant.ns_prefix.as_ref()),
+    }
+}
+
+/// Generates an `xml_struct::QName` for a name in a namespace, which is
+/// qualified with a prefix bound to the namespace at the time of serialization.
+fn qualified_name_tokens(name: &str, ns_name: &TokenStream) -> TokenStream {
+    quote!(::xml_struct::QName::with_ns(#ns_name, #name))
+}
+
+/// Generates tokens for a name in Clark notation, i.e. `{namespace}Name`.
+fn clark_name_tokens(name: &str, ns_name: &TokenStream) -> TokenStream {
+    generate_static_string_concat(
+        generate_static_string_concat(Literal::string("{"), ns_name),
+        Literal::string(&format!("}}{name}")),
+    )
+}
+
+/// Generates tokens for a name string, prefixed with a namespace if
+/// appropriate.
+fn name_to_string_tokens(name: &str, ns_prefix: Option<&TokenStream>) -> TokenStream {
+    // If the consumer has specified a namespace prefix, we need to statically
+    // concatenate the prefix with the name. Otherwise, we can use the name as
+    // a literal.
+    if let Some(prefix) = ns_prefix {
+        let string_with_colon = format!(":{name}");
+        generate_static_string_concat(prefix, Literal::string(&string_with_colon))
+    } else {
+        Literal::string(name).into_token_stream()
+    }
+}
+
+impl RenameRule {
+    /// Converts the identifier of a field, expected to be in snake_case, to
+    /// this case convention.
+    pub(crate) fn apply_to_field(self, ident: &Ident) -> String {
+        let name = ident.unraw().to_string();
+
+        match self {
+            Self::PascalCase => snake_to_pascal(&name),
+            Self::CamelCase => lowercase_first(&snake_to_pascal(&name)),
+            Self::SnakeCase => name,
+            Self::KebabCase => name.replace('_', "-"),
+            Self::Lowercase => name.to_ascii_lowercase(),
+            Self::Uppercase => name.to_ascii_uppercase(),
+        }
+    }
+
+    /// Converts the identifier of an enum variant, expected to be in
+    /// PascalCase, to this case convention.
+    pub(crate) fn apply_to_variant(self, ident: &Ident) -> String {
+        let name = ident.unraw().to_string();
+
+        match self {
+            Self::PascalCase => name,
+            Self::CamelCase => lowercase_first(&name),
+            Self::SnakeCase => pascal_to_separated(&name, '_'),
+            Self::KebabCase => pascal_to_separated(&name, '-'),
+            Self::Lowercase => name.to_ascii_lowercase(),
+            Self::Uppercase => name.to_ascii_uppercase(),
+        }
+    }
+}
+
+// Rust supports non-ASCII identifiers, so the case mapping below could
+// technically fail, but this macro does not currently handle the general XML
+// case, and so full Unicode case mapping is out of scope at present.
+
+/// Converts a snake_case identifier string to PascalCase.
+fn snake_to_pascal(snake: &str) -> String {
+    let mut capitalize_next = true;
+
+    snake
+        .chars()
+        .filter_map(|character| {
+            if character == '_' {
+                // Consume the underscore and capitalize the next character.
+                capitalize_next = true;
+
+                None
+            } else if capitalize_next {
+                capitalize_next = false;
+
+                Some(character.to_ascii_uppercase())
+            } else {
+                Some(character)
+            }
+        })
+        .collect()
+}
+
+/// Converts a PascalCase identifier string to lowercase words joined by the
+/// given separator.
+fn pascal_to_separated(pascal: &str, separator: char) -> String {
+    let mut separated = String::with_capacity(pascal.len());
+
+    for (idx, character) in pascal.chars().enumerate() {
+        if character.is_ascii_uppercase() && idx != 0 {
+            separated.push(separator);
+        }
+
+        separated.push(character.to_ascii_lowercase());
+    }
+
+    separated
+}
+
+/// Converts the first character of a string to lowercase.
+fn lowercase_first(value: &str) -> String {
+    let mut chars = value.chars();
+
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Generates code for concatenating strings at compile-time.
+///
+/// This code allows for concatenating `const` string references and/or string
+/// literals with zero runtime cost.
+pub(crate) fn generate_static_string_concat<T, U>(a: T, b: U) -> TokenStream
+where
+    T: ToTokens,
+    U: ToTokens,
+{
+    quote!({
+        const LEN: usize = #a.len() + #b.len();
+
+        const fn copy_bytes_into(input: &[u8], mut output: [u8; LEN], offset: usize) -> [u8; LEN] {
+            // Copy the input byte-by-byte into the output buffer at the
+            // specified offset.
+            // NOTE: If/when `const_for` is stabilized, this can become a `for`
+            // loop. https://github.com/rust-lang/rust/issues/87575
+            let mut index = 0;
+            loop {
+                output[offset + index] = input[index];
+                index += 1;
+                if index == input.len() {
+                    break;
+                }
+            }
+
+            // We must return the buffer, as `const` functions cannot take a
+            // mutable reference, so it's moved into and out of scope.
+            output
+        }
+
+        const fn constcat(prefix: &'static str, value: &'static str) -> [u8; LEN] {
+            let mut output = [0u8; LEN];
+            output = copy_bytes_into(prefix.as_bytes(), output, 0);
+            output = copy_bytes_into(value.as_bytes(), output, prefix.len());
+
+            output
+        }
+
+        // As of writing this comment, Rust does not provide a standard macro
+        // for compile-time string concatenation, so we exploit the fact that
+        // `str::as_bytes()` and `std::str::from_utf8()` are `const`.
+        const BYTES: [u8; LEN] = constcat(#a, #b);
+        match std::str::from_utf8(&BYTES) {
+            Ok(value) => value,
+
+            // Given that both inputs to `constcat()` are Rust strings, they're
+            // guaranteed to be valid UTF-8. As such, directly concatenating
+            // them should create valid UTF-8 as well. If we hit this panic,
+            // it's probably a bug in one of the above functions.
+            Err(_) => panic!("Unable to statically concatenate strings"),
+        }
+    })
+}
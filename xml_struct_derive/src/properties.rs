@@ -2,11 +2,13 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use proc_macro2::TokenStream;
-use quote::ToTokens as _;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens as _};
 use syn::{
-    punctuated::Punctuated, spanned::Spanned as _, Attribute, DeriveInput, Error, Expr, Meta, Token,
+    punctuated::Punctuated, spanned::Spanned as _, Attribute, DeriveInput, Error, Expr, ExprLit,
+    ExprPath, Lit, Meta, Token,
 };
+use xml_struct_productions::{is_name, is_ncname, XMLNS_NAMESPACE, XML_NAMESPACE};
 
 use crate::MACRO_ATTRIBUTE;
 
@@ -44,6 +46,32 @@ pub(crate) struct TypeProps {
     ///
     /// This property is invalid for structs or text enums.
     pub ns_prefix_for_variants: Option<TokenStream>,
+
+    /// The name of a namespace in which to name the elements representing named
+    /// fields of this type and its enum variants, unless otherwise specified.
+    ///
+    /// Unlike a prefixed namespace declaration, the prefix for this namespace
+    /// is chosen during serialization.
+    pub ns_name: Option<TokenStream>,
+
+    /// The case convention used to derive XML names from the identifiers of
+    /// fields and enum variants.
+    pub rename_all: RenameRule,
+
+    /// The type of XML structure which named fields represent if not otherwise
+    /// specified.
+    pub default_field_repr: FieldRepr,
+
+    /// Whether fields represented as sibling elements with the same name should
+    /// be rejected.
+    ///
+    /// Fields represented as attributes with the same name are always
+    /// rejected.
+    pub should_deny_duplicate_elements: bool,
+
+    /// Whether child elements remaining once all fields have been deserialized
+    /// should be rejected.
+    pub should_deny_unknown_elements: bool,
 }
 
 impl TypeProps {
@@ -66,6 +94,16 @@ impl TypeProps {
         // `xml_struct` attribute to modify any property which deviates from the
         // default.
         let mut properties = TypeProps::default();
+
+        // Unlike other properties, the case convention and default field
+        // representation have non-`Option` defaults, so we track whether they
+        // have been set separately.
+        let mut has_rename_all = false;
+        let mut has_default_field_repr = false;
+
+        // Prefixes declared as string literals, which we track in order to
+        // catch duplicate declarations.
+        let mut declared_prefixes: Vec<String> = Vec::new();
         for meta in attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
             match meta {
                 Meta::Path(path) => {
@@ -95,6 +133,32 @@ impl TypeProps {
                                 "only unit enums may be derived as text",
                             ))
                         }
+                    } else if path.is_ident("deny_duplicate_elements") {
+                        // The consumer has asked that fields of the type to
+                        // which this is applied which share an element name be
+                        // treated as an error, as they can't be distinguished
+                        // when deserializing.
+                        if properties.should_deny_duplicate_elements {
+                            errors.push(Error::new(
+                                path.span(),
+                                "cannot declare `deny_duplicate_elements` more than once",
+                            ));
+                        }
+
+                        properties.should_deny_duplicate_elements = true;
+                    } else if path.is_ident("deny_unknown_elements") {
+                        // The consumer has asked that child elements which
+                        // aren't read into any field of the type to which this
+                        // is applied be treated as an error when
+                        // deserializing, rather than ignored.
+                        if properties.should_deny_unknown_elements {
+                            errors.push(Error::new(
+                                path.span(),
+                                "cannot declare `deny_unknown_elements` more than once",
+                            ));
+                        }
+
+                        properties.should_deny_unknown_elements = true;
                     } else {
                         errors.push(Error::new(path.span(), UNRECOGNIZED_ATTRIBUTE_MSG));
                     }
@@ -114,6 +178,10 @@ impl TypeProps {
                             }
 
                             None => {
+                                if let Err(err) = validate_default_ns_name(&name_value.value) {
+                                    errors.push(err);
+                                }
+
                                 properties.default_ns_name =
                                     Some(name_value.value.to_token_stream())
                             }
@@ -124,21 +192,57 @@ impl TypeProps {
                         // a namespace with prefix, e.g. `xmlns:foo="bar"`.
                         // There can be many of these attributes per type.
                         //
-                        // Ideally, we could prevent duplicate namespace prefixes here,
-                        // but allowing consumers to pass either by variable or by
-                        // literal makes that exceedingly difficult.
+                        // Prefixes and names passed as literals are checked
+                        // here, including for duplicate prefixes. Those passed
+                        // by variable can't be known until runtime.
+                        //
+                        // Alternatively, a namespace name alone specifies the
+                        // namespace of the type's fields and variants, with a
+                        // prefix chosen during serialization.
                         match &name_value.value {
                             Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+                                let (prefix, name) = (&tuple.elems[0], &tuple.elems[1]);
+
+                                if let Err(err) = validate_ns_decl(prefix, name) {
+                                    errors.push(err);
+                                }
+
+                                if let Some(prefix_value) = string_literal_value(prefix) {
+                                    if declared_prefixes.contains(&prefix_value) {
+                                        errors.push(Error::new(
+                                            prefix.span(),
+                                            format!("cannot declare namespace prefix `{prefix_value}` more than once"),
+                                        ));
+                                    } else {
+                                        declared_prefixes.push(prefix_value);
+                                    }
+                                }
+
                                 properties.ns_decls.push(NamespaceDecl {
-                                    prefix: tuple.elems[0].to_token_stream(),
-                                    name: tuple.elems[1].to_token_stream(),
+                                    prefix: prefix.to_token_stream(),
+                                    name: name.to_token_stream(),
                                 })
                             }
 
-                            unexpected => errors.push(Error::new(
-                                unexpected.span(),
-                                "namespace value must be a tuple of exactly two elements",
+                            Expr::Tuple(tuple) => errors.push(Error::new(
+                                tuple.span(),
+                                "namespace value must be a namespace name or a tuple of exactly two elements",
                             )),
+
+                            ns_name => {
+                                if properties.ns_name.is_some() {
+                                    errors.push(Error::new(
+                                        ns_name.span(),
+                                        "cannot declare more than one namespace name",
+                                    ));
+                                } else {
+                                    if let Err(err) = validate_ns_name(ns_name) {
+                                        errors.push(err);
+                                    }
+
+                                    properties.ns_name = Some(ns_name.to_token_stream());
+                                }
+                            }
                         }
                     } else if name_value.path.is_ident("variant_ns_prefix") {
                         // When serialized as an element, values of the enum
@@ -153,6 +257,10 @@ impl TypeProps {
                             }
                             None => match &input.data {
                                 syn::Data::Enum(_) => {
+                                    if let Err(err) = validate_ns_prefix(&name_value.value) {
+                                        errors.push(err);
+                                    }
+
                                     properties.ns_prefix_for_variants =
                                         Some(name_value.value.to_token_stream());
                                 }
@@ -165,6 +273,40 @@ impl TypeProps {
                                 }
                             },
                         }
+                    } else if name_value.path.is_ident("rename_all") {
+                        // Names of fields and variants of the type to which
+                        // this is applied should be converted to the specified
+                        // case convention rather than the default.
+                        if has_rename_all {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one case convention",
+                            ));
+                        }
+
+                        match RenameRule::try_from_expr(&name_value.value) {
+                            Ok(rule) => properties.rename_all = rule,
+                            Err(err) => errors.push(err),
+                        }
+
+                        has_rename_all = true;
+                    } else if name_value.path.is_ident("fields") {
+                        // Named fields of the type to which this is applied
+                        // should be represented as the specified type of XML
+                        // structure unless otherwise marked.
+                        if has_default_field_repr {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one default field representation",
+                            ));
+                        }
+
+                        match FieldRepr::try_from_expr(&name_value.value) {
+                            Ok(repr) => properties.default_field_repr = repr,
+                            Err(err) => errors.push(err),
+                        }
+
+                        has_default_field_repr = true;
                     } else {
                         errors.push(Error::new(name_value.span(), UNRECOGNIZED_ATTRIBUTE_MSG));
                     }
@@ -176,8 +318,9 @@ impl TypeProps {
             }
         }
 
-        let has_namespace_decl =
-            properties.default_ns_name.is_some() || !properties.ns_decls.is_empty();
+        let has_namespace_decl = properties.default_ns_name.is_some()
+            || !properties.ns_decls.is_empty()
+            || properties.ns_name.is_some();
         if has_namespace_decl && properties.should_serialize_as_text {
             // There's no meaningful way to namespace text content, so the
             // combination of these properties is almost certainly a mistake.
@@ -235,15 +378,79 @@ pub(crate) struct FieldProps {
     /// A prefix to add to this field's name when serialized as an element or
     /// attribute.
     pub namespace_prefix: Option<TokenStream>,
+
+    /// The name of a namespace in which to name this field when serialized as
+    /// an element or attribute, with a prefix chosen during serialization.
+    pub ns_name: Option<TokenStream>,
+
+    /// A name to use for this field in place of one derived from its
+    /// identifier.
+    pub rename: Option<String>,
+
+    /// Whether the field should be omitted from serialized output.
+    ///
+    /// A skipped field is deserialized as its type's default value.
+    pub should_skip: bool,
+
+    /// A path to a function determining whether the field should be omitted
+    /// from serialized output.
+    ///
+    /// The function is called with a reference to the value of the field and
+    /// should return `true` if the field is to be omitted.
+    pub skip_serializing_if: Option<TokenStream>,
+
+    /// Whether the field's text should be serialized as CDATA sections rather
+    /// than as escaped text.
+    pub should_use_cdata: bool,
+
+    /// The name of the element enclosing each entry when the field is a
+    /// collection.
+    pub item_name: Option<String>,
+
+    /// Whether each entry of a collection field should be serialized as a
+    /// sibling element named for the field, without an enclosing element.
+    pub should_repeat: bool,
+
+    /// The form in which the entries of a map field should be serialized, if
+    /// not that of the map type's own implementation.
+    pub map_repr: Option<MapRepr>,
+
+    /// The name of the attribute containing each entry's key when a map
+    /// field's entries are serialized as elements.
+    pub map_key_name: Option<String>,
+
+    /// The separator between entries when a collection field is serialized as
+    /// a list within a single attribute value.
+    pub list_separator: Option<String>,
+
+    /// A replacement for the field type's `XmlSerialize` or `XmlSerializeAttr`
+    /// implementation.
+    pub serialize_with: Option<FieldHook>,
+
+    /// A replacement for the field type's `XmlDeserialize` or
+    /// `XmlDeserializeAttr` implementation.
+    pub deserialize_with: Option<FieldHook>,
 }
 
 impl FieldProps {
     /// Constructs a set of serialization properties for an enum or struct field
     /// from its struct attributes.
+    ///
+    /// Named fields which are neither flattened nor explicitly marked as an
+    /// attribute or element are represented as `default_repr`.
     pub(crate) fn try_from_attrs(
         value: Vec<Attribute>,
         field_has_name: bool,
+        default_repr: FieldRepr,
     ) -> Result<Self, Error> {
+        // Unnamed fields can only be represented as content, so the default
+        // representation only applies to named fields.
+        let default_repr = if field_has_name {
+            default_repr
+        } else {
+            FieldRepr::Element
+        };
+
         // Find the attribute for configuring behavior of the derivation, if
         // any.
         let attr = match find_configuration_attribute(&value) {
@@ -251,7 +458,12 @@ impl FieldProps {
 
             // If we don't find a matching attribute, we assume the default set
             // of properties.
-            None => return Ok(Self::default()),
+            None => {
+                return Ok(Self {
+                    repr: default_repr,
+                    ..Default::default()
+                })
+            }
         };
 
         // We build a list of errors so that we can combine them later and emit
@@ -262,10 +474,14 @@ impl FieldProps {
         // `xml_struct` attribute to modify any property which deviates from the
         // default.
         let mut properties = FieldProps::default();
+        let mut has_explicit_repr = false;
+        let mut has_text_repr = false;
         for meta in attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
             match meta {
                 Meta::Path(path) => {
                     if path.is_ident("attribute") {
+                        has_explicit_repr = true;
+
                         // The name of the field is used as the XML attribute
                         // name, so unnamed fields (e.g., members of tuple
                         // structs) cannot be represented as attributes.
@@ -278,9 +494,33 @@ impl FieldProps {
                             ))
                         }
                     } else if path.is_ident("element") {
+                        has_explicit_repr = true;
                         properties.repr = FieldRepr::Element;
+                    } else if path.is_ident("text") {
+                        has_text_repr = true;
                     } else if path.is_ident("flatten") {
                         properties.should_flatten = true;
+                    } else if path.is_ident("repeated") {
+                        // Repeated entries are named for the field, so unnamed
+                        // fields cannot be repeated.
+                        if field_has_name {
+                            properties.should_repeat = true;
+                        } else {
+                            errors.push(Error::new(path.span(), "cannot repeat unnamed field"));
+                        }
+                    } else if path.is_ident("cdata") {
+                        properties.should_use_cdata = true;
+                    } else if path.is_ident("skip") {
+                        properties.should_skip = true;
+                    } else if path.is_ident("list") {
+                        if properties.list_separator.is_some() {
+                            errors.push(Error::new(
+                                path.span(),
+                                "cannot declare more than one list separator",
+                            ));
+                        } else {
+                            properties.list_separator = Some(String::from(DEFAULT_LIST_SEPARATOR));
+                        }
                     } else {
                         errors.push(Error::new(path.span(), UNRECOGNIZED_ATTRIBUTE_MSG));
                     }
@@ -293,10 +533,154 @@ impl FieldProps {
                                 "cannot declare more than one namespace prefix",
                             )),
                             None => {
+                                if let Err(err) = validate_ns_prefix(&name_value.value) {
+                                    errors.push(err);
+                                }
+
                                 properties.namespace_prefix =
                                     Some(name_value.value.to_token_stream());
                             }
                         }
+                    } else if name_value.path.is_ident("ns") {
+                        if properties.ns_name.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one namespace name",
+                            ));
+                        } else {
+                            if let Err(err) = validate_ns_name(&name_value.value) {
+                                errors.push(err);
+                            }
+
+                            properties.ns_name = Some(name_value.value.to_token_stream());
+                        }
+                    } else if name_value.path.is_ident("skip_serializing_if") {
+                        if properties.skip_serializing_if.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one skip condition",
+                            ));
+                        } else {
+                            match expect_path_literal(&name_value.value) {
+                                Ok(path) => properties.skip_serializing_if = Some(path),
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("serialize_with") {
+                        if properties.serialize_with.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one serialization function",
+                            ));
+                        } else {
+                            match expect_path_literal(&name_value.value) {
+                                Ok(path) => {
+                                    properties.serialize_with = Some(FieldHook::Function(path))
+                                }
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("deserialize_with") {
+                        if properties.deserialize_with.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one deserialization function",
+                            ));
+                        } else {
+                            match expect_path_literal(&name_value.value) {
+                                Ok(path) => {
+                                    properties.deserialize_with = Some(FieldHook::Function(path))
+                                }
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("with") {
+                        if properties.serialize_with.is_some()
+                            || properties.deserialize_with.is_some()
+                        {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one serialization or deserialization function",
+                            ));
+                        } else {
+                            match expect_path_literal(&name_value.value) {
+                                Ok(path) => {
+                                    properties.serialize_with =
+                                        Some(FieldHook::Module(path.clone()));
+                                    properties.deserialize_with = Some(FieldHook::Module(path));
+                                }
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("map") {
+                        if properties.map_repr.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one map representation",
+                            ));
+                        } else {
+                            match MapRepr::try_from_expr(&name_value.value) {
+                                Ok(repr) => properties.map_repr = Some(repr),
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("key") {
+                        if properties.map_key_name.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one key name",
+                            ));
+                        } else {
+                            match expect_name_literal(&name_value.value) {
+                                Ok(name) => properties.map_key_name = Some(name),
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("list") {
+                        if properties.list_separator.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one list separator",
+                            ));
+                        } else {
+                            match expect_string_literal(&name_value.value) {
+                                Ok(separator) if separator.is_empty() => errors.push(Error::new(
+                                    name_value.value.span(),
+                                    "list separator must not be empty",
+                                )),
+                                Ok(separator) => properties.list_separator = Some(separator),
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("item") {
+                        if properties.item_name.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one item name",
+                            ));
+                        } else {
+                            match expect_name_literal(&name_value.value) {
+                                Ok(name) => properties.item_name = Some(name),
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                    } else if name_value.path.is_ident("rename") {
+                        if !field_has_name {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot rename unnamed field",
+                            ));
+                        } else if properties.rename.is_some() {
+                            errors.push(Error::new(
+                                name_value.path.span(),
+                                "cannot declare more than one name",
+                            ));
+                        } else {
+                            match expect_name_literal(&name_value.value) {
+                                Ok(name) => properties.rename = Some(name),
+                                Err(err) => errors.push(err),
+                            }
+                        }
                     } else {
                         errors.push(Error::new(name_value.span(), UNRECOGNIZED_ATTRIBUTE_MSG));
                     }
@@ -312,6 +696,458 @@ impl FieldProps {
             errors.push(Error::new(attr.span(), "cannot flatten attribute fields"));
         }
 
+        if properties.should_use_cdata {
+            if matches!(properties.repr, FieldRepr::Attribute) {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot serialize attribute field as CDATA",
+                ));
+            }
+
+            if properties.serialize_with.is_some() {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine CDATA with a serialization function",
+                ));
+            }
+        }
+
+        if properties.item_name.is_some() && properties.map_repr.is_none() {
+            if matches!(properties.repr, FieldRepr::Attribute) {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot serialize attribute field as a list of items",
+                ));
+            }
+
+            if properties.serialize_with.is_some() || properties.deserialize_with.is_some() {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine item name with a serialization or deserialization function",
+                ));
+            }
+
+            if properties.should_use_cdata {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine item name with CDATA",
+                ));
+            }
+        }
+
+        if let Some(map_repr) = properties.map_repr {
+            let is_valid_repr = match map_repr {
+                MapRepr::Attributes => {
+                    !properties.should_flatten
+                        && !has_text_repr
+                        && (!has_explicit_repr || matches!(properties.repr, FieldRepr::Attribute))
+                }
+                MapRepr::Elements | MapRepr::Entries => {
+                    !matches!(properties.repr, FieldRepr::Attribute)
+                }
+            };
+
+            if !is_valid_repr {
+                errors.push(Error::new(
+                    attr.span(),
+                    "map representation is incompatible with field representation",
+                ));
+            }
+
+            if properties.item_name.is_some() && !matches!(map_repr, MapRepr::Entries) {
+                errors.push(Error::new(
+                    attr.span(),
+                    "item names are only valid for maps represented as entries",
+                ));
+            }
+
+            if properties.should_repeat
+                || properties.should_use_cdata
+                || properties.serialize_with.is_some()
+                || properties.deserialize_with.is_some()
+            {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine map representation with repeated elements, CDATA, or serialization functions",
+                ));
+            }
+
+            // Map entries are flattened into attributes on the parent element,
+            // so the field must be represented as an attribute.
+            if matches!(map_repr, MapRepr::Attributes) {
+                if field_has_name {
+                    has_explicit_repr = true;
+                    properties.repr = FieldRepr::Attribute;
+                } else {
+                    errors.push(Error::new(
+                        attr.span(),
+                        "cannot serialize unnamed field as XML attribute",
+                    ));
+                }
+            }
+        }
+
+        if properties.map_key_name.is_some()
+            && !matches!(properties.map_repr, Some(MapRepr::Entries))
+        {
+            errors.push(Error::new(
+                attr.span(),
+                "key names are only valid for maps represented as entries",
+            ));
+        }
+
+        if has_text_repr {
+            if has_explicit_repr || properties.should_flatten {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine text content with other field representations",
+                ));
+            } else {
+                properties.repr = FieldRepr::Text;
+            }
+        } else if !has_explicit_repr
+            && !properties.should_flatten
+            && !properties.should_repeat
+            && properties.map_repr.is_none()
+        {
+            // Flattened fields are represented as content nodes and repeated
+            // fields and maps as elements, so the default representation
+            // doesn't apply to them.
+            properties.repr = default_repr;
+        }
+
+        if properties.list_separator.is_some() {
+            if !matches!(properties.repr, FieldRepr::Attribute) {
+                errors.push(Error::new(
+                    attr.span(),
+                    "list representation is only valid for attribute fields",
+                ));
+            }
+
+            if properties.map_repr.is_some()
+                || properties.serialize_with.is_some()
+                || properties.deserialize_with.is_some()
+            {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine list representation with map representation or serialization functions",
+                ));
+            }
+        }
+
+        if properties.ns_name.is_some() {
+            // Only fields represented by a node named for the field can be
+            // placed in a namespace.
+            let is_named_node = match properties.repr {
+                FieldRepr::Attribute => !matches!(properties.map_repr, Some(MapRepr::Attributes)),
+                FieldRepr::Element => !properties.should_flatten,
+                FieldRepr::Text => false,
+            };
+
+            if !field_has_name || !is_named_node {
+                errors.push(Error::new(
+                    attr.span(),
+                    "namespace names are only valid for named fields serialized as elements or attributes",
+                ));
+            }
+
+            if properties.namespace_prefix.is_some() {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine namespace name with namespace prefix",
+                ));
+            }
+        }
+
+        if properties.should_repeat {
+            if !matches!(properties.repr, FieldRepr::Element) || properties.should_flatten {
+                errors.push(Error::new(
+                    attr.span(),
+                    "repeated fields must be serialized as elements",
+                ));
+            }
+
+            if properties.item_name.is_some()
+                || properties.should_use_cdata
+                || properties.serialize_with.is_some()
+                || properties.deserialize_with.is_some()
+            {
+                errors.push(Error::new(
+                    attr.span(),
+                    "cannot combine repeated elements with item names, CDATA, or serialization functions",
+                ));
+            }
+        }
+
+        // Combine and return errors if there are any. If none, we've
+        // successfully parsed the attributes and can return the appropriate
+        // props.
+        match errors.into_iter().reduce(|mut combined, err| {
+            combined.combine(err);
+
+            combined
+        }) {
+            Some(err) => Err(err),
+            None => Ok(properties),
+        }
+    }
+}
+
+/// Verifies that a field representing the text content of an element is not
+/// accompanied by any other fields representing content nodes of that element.
+///
+/// Each field is given as its properties and the span to use for any error
+/// relating to it.
+pub(crate) fn validate_text_content_fields<'a, I>(fields: I) -> Result<(), Error>
+where
+    I: IntoIterator<Item = (&'a FieldProps, Span)>,
+{
+    let mut text_field_span = None;
+    let mut content_field_spans = Vec::new();
+    for (props, span) in fields {
+        if props.should_skip {
+            continue;
+        }
+
+        match props.repr {
+            FieldRepr::Attribute => (),
+            FieldRepr::Text if text_field_span.is_none() => text_field_span = Some(span),
+            FieldRepr::Text | FieldRepr::Element => content_field_spans.push(span),
+        }
+    }
+
+    if text_field_span.is_none() {
+        return Ok(());
+    }
+
+    content_field_spans
+        .into_iter()
+        .map(|span| {
+            Error::new(
+                span,
+                "cannot combine text content field with other child node fields",
+            )
+        })
+        .reduce(|mut combined, err| {
+            combined.combine(err);
+
+            combined
+        })
+        .map_or(Ok(()), Err)
+}
+
+#[derive(Debug)]
+/// A consumer-provided replacement for the trait implementation used to
+/// serialize or deserialize a field.
+pub(crate) enum FieldHook {
+    /// A path to a single function, which must have the same signature as the
+    /// trait method it replaces.
+    Function(TokenStream),
+
+    /// A path to a module containing functions named for, and with the same
+    /// signatures as, each of the trait methods it replaces.
+    Module(TokenStream),
+}
+
+impl FieldHook {
+    /// Gets a path to the function replacing the named trait method.
+    pub(crate) fn path_for(&self, method: &str) -> TokenStream {
+        match self {
+            Self::Function(path) => path.clone(),
+            Self::Module(module) => {
+                let method = Ident::new(method, Span::call_site());
+
+                quote!(#module::#method)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+/// Properties governing the serialization of an enum variant with a derived
+/// `XmlSerialize` implementation.
+pub(crate) struct VariantProps {
+    /// A name to use for this variant in place of one derived from its
+    /// identifier.
+    ///
+    /// For text enums, this is the text value representing the variant.
+    pub rename: Option<String>,
+
+    /// A declaration of a name for the default XML namespace on the element
+    /// representing this variant, in place of any declared by the enum.
+    pub default_ns_name: Option<TokenStream>,
+
+    /// Declarations of XML namespaces on the element representing this
+    /// variant.
+    ///
+    /// A declaration of a prefix also declared by the enum takes its place.
+    pub ns_decls: Vec<NamespaceDecl>,
+
+    /// A namespace prefix to apply to the tag representing this variant, in
+    /// place of any applied to the enum's variants.
+    pub namespace_prefix: Option<TokenStream>,
+
+    /// The name of a namespace in which to name the element representing this
+    /// variant, in place of any given for the enum.
+    pub ns_name: Option<TokenStream>,
+}
+
+impl VariantProps {
+    /// Constructs a set of serialization properties for an enum variant from
+    /// its attributes.
+    ///
+    /// Variants of text enums are not represented by elements, and so may not
+    /// be given namespaces.
+    pub(crate) fn try_from_attrs(
+        value: &[Attribute],
+        is_text_variant: bool,
+    ) -> Result<Self, Error> {
+        let attr = match find_configuration_attribute(value) {
+            Some(attr) => attr,
+
+            // If we don't find a matching attribute, we assume the default set
+            // of properties.
+            None => return Ok(Self::default()),
+        };
+
+        // We build a list of errors so that we can combine them later and emit
+        // them all instead of only emitting the first.
+        let mut errors = Vec::new();
+
+        // Literal prefixes declared so far, for detecting duplicates.
+        let mut declared_prefixes = Vec::new();
+
+        let mut properties = VariantProps::default();
+        for meta in attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
+            match meta {
+                Meta::NameValue(name_value) if name_value.path.is_ident("rename") => {
+                    if properties.rename.is_some() {
+                        errors.push(Error::new(
+                            name_value.path.span(),
+                            "cannot declare more than one name",
+                        ));
+                    } else {
+                        // Text variants are written as values rather than
+                        // element names, so any string will do.
+                        let name = if is_text_variant {
+                            expect_string_literal(&name_value.value)
+                        } else {
+                            expect_name_literal(&name_value.value)
+                        };
+
+                        match name {
+                            Ok(name) => properties.rename = Some(name),
+                            Err(err) => errors.push(err),
+                        }
+                    }
+                }
+
+                Meta::NameValue(name_value) if name_value.path.is_ident("default_ns") => {
+                    if properties.default_ns_name.is_some() {
+                        errors.push(Error::new(
+                            name_value.path.span(),
+                            "cannot declare more than one default namespace",
+                        ));
+                    } else {
+                        if let Err(err) = validate_default_ns_name(&name_value.value) {
+                            errors.push(err);
+                        }
+
+                        properties.default_ns_name = Some(name_value.value.to_token_stream());
+                    }
+                }
+
+                // As for types, a namespace is either declared with a prefix
+                // or given by name alone, with a prefix chosen during
+                // serialization.
+                Meta::NameValue(name_value) if name_value.path.is_ident("ns") => {
+                    match &name_value.value {
+                        Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+                            let (prefix, name) = (&tuple.elems[0], &tuple.elems[1]);
+
+                            if let Err(err) = validate_ns_decl(prefix, name) {
+                                errors.push(err);
+                            }
+
+                            if let Some(prefix_value) = string_literal_value(prefix) {
+                                if declared_prefixes.contains(&prefix_value) {
+                                    errors.push(Error::new(
+                                        prefix.span(),
+                                        format!("cannot declare namespace prefix `{prefix_value}` more than once"),
+                                    ));
+                                } else {
+                                    declared_prefixes.push(prefix_value);
+                                }
+                            }
+
+                            properties.ns_decls.push(NamespaceDecl {
+                                prefix: prefix.to_token_stream(),
+                                name: name.to_token_stream(),
+                            })
+                        }
+
+                        Expr::Tuple(tuple) => errors.push(Error::new(
+                            tuple.span(),
+                            "namespace value must be a namespace name or a tuple of exactly two elements",
+                        )),
+
+                        ns_name => {
+                            if properties.ns_name.is_some() {
+                                errors.push(Error::new(
+                                    ns_name.span(),
+                                    "cannot declare more than one namespace name",
+                                ));
+                            } else {
+                                if let Err(err) = validate_ns_name(ns_name) {
+                                    errors.push(err);
+                                }
+
+                                properties.ns_name = Some(ns_name.to_token_stream());
+                            }
+                        }
+                    }
+                }
+
+                Meta::NameValue(name_value) if name_value.path.is_ident("ns_prefix") => {
+                    if properties.namespace_prefix.is_some() {
+                        errors.push(Error::new(
+                            name_value.path.span(),
+                            "cannot declare more than one namespace prefix",
+                        ));
+                    } else {
+                        if let Err(err) = validate_ns_prefix(&name_value.value) {
+                            errors.push(err);
+                        }
+
+                        properties.namespace_prefix = Some(name_value.value.to_token_stream());
+                    }
+                }
+
+                _ => {
+                    errors.push(Error::new(meta.span(), UNRECOGNIZED_ATTRIBUTE_MSG));
+                }
+            }
+        }
+
+        let has_namespace = properties.default_ns_name.is_some()
+            || !properties.ns_decls.is_empty()
+            || properties.namespace_prefix.is_some()
+            || properties.ns_name.is_some();
+
+        if is_text_variant && has_namespace {
+            errors.push(Error::new(
+                attr.span(),
+                "cannot declare namespaces for variants of text enum",
+            ));
+        }
+
+        if properties.namespace_prefix.is_some() && properties.ns_name.is_some() {
+            errors.push(Error::new(
+                attr.span(),
+                "cannot combine namespace name with namespace prefix",
+            ));
+        }
+
         // Combine and return errors if there are any. If none, we've
         // successfully parsed the attributes and can return the appropriate
         // props.
@@ -333,6 +1169,287 @@ pub(crate) enum FieldRepr {
 
     #[default]
     Element,
+
+    /// The text content of the element representing the containing structure.
+    ///
+    /// This representation can only be specified for individual fields.
+    Text,
+}
+
+impl FieldRepr {
+    /// Parses a field representation from the value of a `fields` attribute.
+    fn try_from_expr(expr: &Expr) -> Result<Self, Error> {
+        match expect_string_literal(expr)?.as_str() {
+            "attribute" => Ok(Self::Attribute),
+            "element" => Ok(Self::Element),
+
+            _ => Err(Error::new(
+                expr.span(),
+                "unrecognized field representation; expected \"attribute\" or \"element\"",
+            )),
+        }
+    }
+}
+
+/// The name of the element representing each entry of a map field when
+/// serialized as entries, if not otherwise specified.
+pub(crate) const DEFAULT_MAP_ENTRY_NAME: &str = "Entry";
+
+/// The name of the attribute containing each entry's key when a map field is
+/// serialized as entries, if not otherwise specified.
+pub(crate) const DEFAULT_MAP_KEY_NAME: &str = "Key";
+
+/// The separator between entries of a list attribute when none is specified,
+/// matching that of an XML Schema `xs:list`.
+pub(crate) const DEFAULT_LIST_SEPARATOR: &str = " ";
+
+#[derive(Clone, Copy, Debug)]
+/// The forms in which the entries of a map field may be serialized.
+pub(crate) enum MapRepr {
+    /// Each value is an element named by its key.
+    Elements,
+
+    /// Each entry is an element with a common name, with the key as an
+    /// attribute and the value as content.
+    Entries,
+
+    /// Each value is an attribute on the parent element named by its key.
+    Attributes,
+}
+
+impl MapRepr {
+    /// Parses a map representation from the value of a `map` attribute.
+    fn try_from_expr(expr: &Expr) -> Result<Self, Error> {
+        match expect_string_literal(expr)?.as_str() {
+            "elements" => Ok(Self::Elements),
+            "entries" => Ok(Self::Entries),
+            "attributes" => Ok(Self::Attributes),
+
+            _ => Err(Error::new(
+                expr.span(),
+                "unrecognized map representation; expected \"elements\", \"entries\", or \"attributes\"",
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// The case conventions which may be used to derive XML names from Rust
+/// identifiers.
+pub(crate) enum RenameRule {
+    #[default]
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    KebabCase,
+    Lowercase,
+    Uppercase,
+}
+
+impl RenameRule {
+    /// Parses a case convention from the value of a `rename_all` attribute.
+    fn try_from_expr(expr: &Expr) -> Result<Self, Error> {
+        let rule = match expect_string_literal(expr)?.as_str() {
+            "PascalCase" => Self::PascalCase,
+            "camelCase" => Self::CamelCase,
+            "snake_case" => Self::SnakeCase,
+            "kebab-case" => Self::KebabCase,
+            "lowercase" => Self::Lowercase,
+            "UPPERCASE" => Self::Uppercase,
+
+            _ => {
+                return Err(Error::new(
+                    expr.span(),
+                    "unrecognized case convention; expected one of \"PascalCase\", \"camelCase\", \"snake_case\", \"kebab-case\", \"lowercase\", or \"UPPERCASE\"",
+                ))
+            }
+        };
+
+        Ok(rule)
+    }
+}
+
+/// Checks a namespace prefix against the rules of Namespaces in XML 1.0.
+///
+/// Only prefixes given as string literals can be checked; any other expression
+/// is accepted as-is.
+fn validate_ns_prefix(expr: &Expr) -> Result<(), Error> {
+    let prefix = match string_literal_value(expr) {
+        Some(prefix) => prefix,
+        None => return Ok(()),
+    };
+
+    if !is_ncname(&prefix) {
+        return Err(Error::new(
+            expr.span(),
+            format!(
+                "`{prefix}` is not a valid namespace prefix; expected an XML name without colons"
+            ),
+        ));
+    }
+
+    if prefix == "xmlns" {
+        return Err(Error::new(
+            expr.span(),
+            "the `xmlns` prefix is reserved for namespace declarations",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks a prefixed namespace declaration against the rules of Namespaces in
+/// XML 1.0.
+///
+/// As with prefixes, only values given as string literals can be checked.
+fn validate_ns_decl(prefix: &Expr, name: &Expr) -> Result<(), Error> {
+    validate_ns_prefix(prefix)?;
+
+    let prefix_value = string_literal_value(prefix);
+    let name_value = match string_literal_value(name) {
+        Some(name) => name,
+
+        // The `xml` prefix may only be bound to its own namespace, which we
+        // can't confirm without knowing the name.
+        None if prefix_value.as_deref() == Some("xml") => {
+            return Err(Error::new(
+                name.span(),
+                format!("the `xml` prefix may only be bound to \"{XML_NAMESPACE}\", which must be given as a literal"),
+            ));
+        }
+        None => return Ok(()),
+    };
+
+    if name_value.is_empty() {
+        return Err(Error::new(name.span(), "namespace name must not be empty"));
+    }
+
+    if name_value == XMLNS_NAMESPACE {
+        return Err(Error::new(
+            name.span(),
+            format!("cannot declare a prefix for the reserved namespace \"{XMLNS_NAMESPACE}\""),
+        ));
+    }
+
+    match prefix_value.as_deref() {
+        Some("xml") if name_value != XML_NAMESPACE => Err(Error::new(
+            name.span(),
+            format!("the `xml` prefix may only be bound to \"{XML_NAMESPACE}\""),
+        )),
+        Some(prefix) if prefix != "xml" && name_value == XML_NAMESPACE => Err(Error::new(
+            name.span(),
+            format!("\"{XML_NAMESPACE}\" may only be bound to the `xml` prefix"),
+        )),
+
+        _ => Ok(()),
+    }
+}
+
+/// Checks the name of a namespace in which fields or variants are named.
+///
+/// As with prefixes, only names given as string literals can be checked.
+fn validate_ns_name(expr: &Expr) -> Result<(), Error> {
+    let name = match string_literal_value(expr) {
+        Some(name) => name,
+        None => return Ok(()),
+    };
+
+    if name.is_empty() {
+        Err(Error::new(expr.span(), "namespace name must not be empty"))
+    } else if name == XMLNS_NAMESPACE {
+        Err(Error::new(
+            expr.span(),
+            format!("cannot name elements or attributes in the reserved namespace \"{XMLNS_NAMESPACE}\""),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks the name of a default namespace declaration.
+///
+/// As with prefixes, only names given as string literals can be checked.
+fn validate_default_ns_name(expr: &Expr) -> Result<(), Error> {
+    let name = match string_literal_value(expr) {
+        Some(name) => name,
+        None => return Ok(()),
+    };
+
+    if name.is_empty() {
+        // An empty `xmlns` attribute undeclares the default namespace rather
+        // than declaring one, which is almost certainly not what's intended.
+        Err(Error::new(
+            expr.span(),
+            "default namespace name must not be empty",
+        ))
+    } else if name == XML_NAMESPACE || name == XMLNS_NAMESPACE {
+        Err(Error::new(
+            expr.span(),
+            format!("cannot declare the reserved namespace \"{name}\" as the default"),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Gets the value of an expression if it is a string literal.
+fn string_literal_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) => Some(value.value()),
+
+        _ => None,
+    }
+}
+
+/// Gets the value of an attribute which must be a string literal.
+fn expect_string_literal(expr: &Expr) -> Result<String, Error> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) => Ok(value.value()),
+
+        unexpected => Err(Error::new(
+            unexpected.span(),
+            "value must be a string literal",
+        )),
+    }
+}
+
+/// Gets the value of an attribute which must be a string literal containing an
+/// XML name.
+fn expect_name_literal(expr: &Expr) -> Result<String, Error> {
+    let name = expect_string_literal(expr)?;
+
+    if name.is_empty() {
+        Err(Error::new(expr.span(), "name must not be empty"))
+    } else if is_name(&name) {
+        Ok(name)
+    } else {
+        Err(Error::new(
+            expr.span(),
+            format!("`{name}` is not a valid XML name"),
+        ))
+    }
+}
+
+/// Gets the value of an attribute which must be a string literal containing a
+/// path, e.g. `"path::to::function"`.
+fn expect_path_literal(expr: &Expr) -> Result<TokenStream, Error> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) => value.parse::<ExprPath>().map(|path| path.to_token_stream()),
+
+        unexpected => Err(Error::new(
+            unexpected.span(),
+            "value must be a string literal containing a path",
+        )),
+    }
 }
 
 /// Gets the attribute containing configuration parameters for this derive
@@ -6,7 +6,14 @@ use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{quote, ToTokens};
 use syn::Generics;
 
-use crate::{FieldProps, FieldRepr, TypeProps};
+use crate::{
+    input::{partition_fields, Field, FieldKind, Fields, Variant, VariantKind},
+    names::{
+        attribute_name_to_string_tokens, field_error_context_tokens, field_name_to_string_tokens,
+        field_qualified_name_tokens, variant_name_to_string_tokens, variant_qualified_name_tokens,
+    },
+    FieldRepr, MapRepr, NamespaceDecl, TypeProps, DEFAULT_MAP_ENTRY_NAME, DEFAULT_MAP_KEY_NAME,
+};
 
 /// Generates an implementation of the `XmlSerialize` trait and, if appropriate,
 /// the `XmlSerializeAttr` trait.
@@ -17,28 +24,17 @@ pub(super) fn generate_serialize_impl_for<G>(
     body_generator: G,
 ) -> TokenStream
 where
-    G: FnOnce(&[XmlAttribute]) -> ImplTokenSets,
+    G: FnOnce(&[NamespaceDeclTokens]) -> ImplTokenSets,
 {
-    let default_ns_attr = props.default_ns_name.map(|ns_name| XmlAttribute {
-        // The terminology is a little confusing here. In terms of the XML
-        // spec, the "name" of a namespace is the (usually) URI used as the
-        // _value_ of the namespace declaration attribute.
-        name: Literal::string("xmlns").into_token_stream(),
-        value: ns_name,
-    });
-
-    let ns_decl_attrs = props.ns_decls.into_iter().map(|ns_decl| XmlAttribute {
-        name: generate_static_string_concat("xmlns:", ns_decl.prefix),
-        value: ns_decl.name,
-    });
-
-    let namespace_attrs: Vec<_> = default_ns_attr.into_iter().chain(ns_decl_attrs).collect();
+    let namespace_decls = namespace_decl_tokens(props.default_ns_name, props.ns_decls);
 
     let ImplTokenSets {
         as_element_impl,
         child_nodes_body,
+        attributes_impl,
+        declared_namespaces_impl,
         as_attr_body,
-    } = body_generator(&namespace_attrs);
+    } = body_generator(&namespace_decls);
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
@@ -46,8 +42,14 @@ where
         quote! {
             #[automatically_derived]
             impl #impl_generics ::xml_struct::XmlSerializeAttr for #type_ident #ty_generics #where_clause {
-                fn serialize_as_attribute(&self, start_tag: &mut ::quick_xml::events::BytesStart, name: &str) {
+                fn serialize_as_attribute(
+                    &self,
+                    start_tag: &mut ::quick_xml::events::BytesStart,
+                    name: &str,
+                ) -> Result<(), ::xml_struct::Error> {
                     #body
+
+                    Ok(())
                 }
             }
         }
@@ -65,12 +67,16 @@ where
 
             fn serialize_child_nodes<W: std::io::Write>(
                 &self,
-                writer: &mut ::quick_xml::writer::Writer<W>
+                writer: &mut ::xml_struct::XmlWriter<W>
             ) -> Result<(), ::xml_struct::Error> {
                 #child_nodes_body
 
                 Ok(())
             }
+
+            #attributes_impl
+
+            #declared_namespaces_impl
         }
 
         #attr_impl
@@ -87,6 +93,14 @@ pub(super) struct ImplTokenSets {
     /// The body of `XmlSerialize::serialize_child_nodes()`.
     child_nodes_body: TokenStream,
 
+    /// The implementation of `XmlSerialize::serialize_attributes()` if it is
+    /// necessary to override the provided default implementation.
+    attributes_impl: TokenStream,
+
+    /// The implementation of `XmlSerialize::declared_namespaces()` if it is
+    /// necessary to override the provided default implementation.
+    declared_namespaces_impl: TokenStream,
+
     /// The body of `XmlSerializeAttr::serialize_as_attribute()` if the type is
     /// capable of being serialized as such.
     as_attr_body: Option<TokenStream>,
@@ -96,29 +110,82 @@ pub(super) struct ImplTokenSets {
 /// with the provided fields.
 pub(super) fn with_struct_fields(
     fields: Vec<Field>,
-) -> impl FnOnce(&[XmlAttribute]) -> ImplTokenSets {
-    move |namespace_attrs| {
+) -> impl FnOnce(&[NamespaceDeclTokens]) -> ImplTokenSets {
+    move |namespace_decls| {
         let Fields {
             attr_fields,
             child_fields,
         } = partition_fields(fields);
 
+        let namespaces = field_namespaces(attr_fields.iter().chain(&child_fields));
+        let content_types = content_node_field_types(&child_fields);
+
+        let attr_calls = generate_attribute_field_calls(&attr_fields, None);
+        let content_attr_calls = generate_content_attribute_calls(&child_fields, None);
+
+        // The struct's attributes are set by `serialize_attributes()`, so that
+        // they are set in the same way on the struct's own element and on the
+        // element enclosing it when it is serialized as content nodes.
+        let (attributes_call, attributes_impl) = if attr_calls.is_empty()
+            && content_attr_calls.is_empty()
+        {
+            (TokenStream::default(), TokenStream::default())
+        } else {
+            (
+                quote! {
+                    <Self as ::xml_struct::XmlSerialize>::serialize_attributes(self, writer, &mut start_tag)
+                        .map_err(|err| err.in_element(name))?;
+                },
+                quote! {
+                    fn serialize_attributes<W: std::io::Write>(
+                        &self,
+                        writer: &::xml_struct::XmlWriter<W>,
+                        start_tag: &mut ::quick_xml::events::BytesStart,
+                    ) -> Result<(), ::xml_struct::Error> {
+                        // The calls below borrow the start tag as they would
+                        // that of an element written by the generated code.
+                        let mut start_tag = start_tag;
+
+                        #attr_calls
+                        #content_attr_calls
+
+                        Ok(())
+                    }
+                },
+            )
+        };
+
         let content_call = if !child_fields.is_empty() {
             Some(quote! {
-                <Self as XmlSerialize>::serialize_child_nodes(self, writer)?;
+                <Self as XmlSerialize>::serialize_child_nodes(self, writer)
+                    .map_err(|err| err.in_element(name))?;
             })
         } else {
             None
         };
 
-        let impl_body =
-            generate_xml_tag_calls(quote!(name), namespace_attrs, &attr_fields, content_call);
+        let impl_body = generate_xml_tag_calls(
+            quote!(name),
+            namespace_decls,
+            &content_types,
+            &namespaces,
+            attributes_call,
+            content_call,
+        );
+
+        // When values of the struct are serialized as content nodes, e.g. as a
+        // flattened field, its namespaces must be declared by the enclosing
+        // element instead.
+        let declared_namespaces: Vec<_> = namespace_decls
+            .iter()
+            .map(|NamespaceDeclTokens { prefix, name }| quote!((#prefix, #name)))
+            .collect();
 
         ImplTokenSets {
             as_element_impl: quote! {
                 fn serialize_as_element<W: std::io::Write>(
                     &self,
-                    writer: &mut ::quick_xml::writer::Writer<W>,
+                    writer: &mut ::xml_struct::XmlWriter<W>,
                     name: &str,
                 ) -> Result<(), ::xml_struct::Error> {
                     #impl_body
@@ -126,7 +193,22 @@ pub(super) fn with_struct_fields(
                     Ok(())
                 }
             },
-            child_nodes_body: generate_field_content_node_calls(child_fields),
+            child_nodes_body: generate_field_content_node_calls(child_fields, None),
+            attributes_impl,
+            declared_namespaces_impl: quote! {
+                fn declared_namespaces(
+                ) -> ::std::vec::Vec<(::core::option::Option<&'static str>, &'static str)> {
+                    let mut namespaces: ::std::vec::Vec<(::core::option::Option<&'static str>, &'static str)> =
+                        ::std::vec![#(#declared_namespaces),*];
+                    #(namespaces.extend(
+                        <#content_types as ::xml_struct::XmlSerialize>::declared_namespaces()
+                            .into_iter()
+                            .filter(|(prefix, _)| prefix.is_some())
+                    );)*
+
+                    namespaces
+                }
+            },
 
             // There is no clear text representation of an arbitrary struct, so
             // we cannot provide an `XmlSerializeAttr` derivation.
@@ -138,16 +220,21 @@ pub(super) fn with_struct_fields(
 /// Creates a generator for the sets of tokens necessary to serialize a
 /// unit-only enum as text nodes or attribute values.
 pub(super) fn with_text_variants(
-    variants: Vec<Ident>,
-) -> impl FnOnce(&[XmlAttribute]) -> ImplTokenSets {
-    // While the generator function takes namespace attributes as its argument,
-    // we expect that the consuming code has already verified that there are
-    // none for this enum, since attributes cannot be specified for text content
-    // nodes.
+    variants: Vec<Variant>,
+) -> impl FnOnce(&[NamespaceDeclTokens]) -> ImplTokenSets {
+    // While the generator function takes namespace declarations as its
+    // argument, we expect that the consuming code has already verified that
+    // there are none for this enum, since attributes cannot be specified for
+    // text content nodes.
     move |_| {
         let match_arms: Vec<_> = variants
             .iter()
-            .map(|variant| quote!(Self::#variant => stringify!(#variant)))
+            .map(|variant| {
+                let ident = &variant.ident;
+                let text = Literal::string(&variant.xml_name);
+
+                quote!(Self::#ident => #text)
+            })
             .collect();
 
         let text_from_value = quote! {
@@ -170,6 +257,8 @@ pub(super) fn with_text_variants(
                     )
                 )?;
             },
+            attributes_impl: TokenStream::default(),
+            declared_namespaces_impl: TokenStream::default(),
             as_attr_body: Some(quote! {
                 #text_from_value
 
@@ -185,72 +274,86 @@ pub(super) fn with_text_variants(
 /// with the provided variants.
 pub(super) fn with_enum_variants(
     variants: Vec<Variant>,
-    ns_prefix: Option<TokenStream>,
-) -> impl FnOnce(&[XmlAttribute]) -> ImplTokenSets {
-    move |namespace_attrs| {
+) -> impl FnOnce(&[NamespaceDeclTokens]) -> ImplTokenSets {
+    move |type_namespace_decls| {
         let match_arms: TokenStream = variants
             .into_iter()
             .map(|variant| {
-                let ident = variant.ident;
-
-                let name_tokens = {
-                    // If the consumer has specified that variants should be
-                    // serialized with a namespace prefix, we need to statically
-                    // concatenate the prefix with the variant name. Otherwise,
-                    // we just need to stringify the variant name.
-                    if let Some(prefix) = &ns_prefix {
-                        let ident_as_str = ident.to_string();
-                        let ident_as_str_tokens = format!(":{ident_as_str}");
-                        generate_static_string_concat(prefix, ident_as_str_tokens)
-                    } else {
-                        quote!(stringify!(#ident))
-                    }
+                let qualified_name = variant_qualified_name_tokens(&variant);
+                let name_tokens = if qualified_name.is_some() {
+                    quote!(variant_name)
+                } else {
+                    variant_name_to_string_tokens(&variant)
                 };
 
-                match variant.kind {
+                // Namespaces declared for the enum are declared on each
+                // variant's element, along with those of the variant itself.
+                let namespace_decls = &merge_namespace_decls(
+                    type_namespace_decls,
+                    namespace_decl_tokens(variant.default_ns_name, variant.ns_decls),
+                );
+
+                let ident = variant.ident;
+
+                let (pattern, content_calls) = match variant.kind {
                     VariantKind::Struct(fields) => {
                         let VariantTokenSets {
                             accessors,
                             body: content_calls,
-                        } = generate_variant_token_sets(name_tokens, namespace_attrs, fields);
+                        } = generate_variant_token_sets(name_tokens, namespace_decls, fields);
 
-                        quote! {
-                            Self::#ident { #(#accessors),* } => {
-                                #content_calls
-                            }
-                        }
+                        (quote!(Self::#ident { #(#accessors),* }), content_calls)
                     }
                     VariantKind::Tuple(fields) => {
                         let VariantTokenSets {
                             accessors,
                             body: content_calls,
-                        } = generate_variant_token_sets(name_tokens, namespace_attrs, fields);
+                        } = generate_variant_token_sets(name_tokens, namespace_decls, fields);
 
-                        quote! {
-                            Self::#ident(#(#accessors),*) => {
-                                #content_calls
-                            }
-                        }
+                        (quote!(Self::#ident(#(#accessors),*)), content_calls)
                     }
                     VariantKind::Unit => {
-                        let content_calls =
-                            generate_xml_tag_calls(name_tokens, namespace_attrs, &[], None);
+                        let content_calls = generate_xml_tag_calls(
+                            name_tokens,
+                            namespace_decls,
+                            &[],
+                            &[],
+                            TokenStream::default(),
+                            None,
+                        );
+
+                        (quote!(Self::#ident), content_calls)
+                    }
+                };
 
-                        quote! {
-                            Self::#ident => {
+                match qualified_name {
+                    // The name of a variant in a namespace is only known once
+                    // serialization begins, and a prefix may need to be
+                    // declared for it on the variant's element.
+                    Some(qualified_name) => quote! {
+                        #pattern => writer.with_element_name(
+                            #qualified_name,
+                            |writer, variant_name| -> ::core::result::Result<(), ::xml_struct::Error> {
                                 #content_calls
-                            }
+
+                                Ok(())
+                            },
+                        )?,
+                    },
+                    None => quote! {
+                        #pattern => {
+                            #content_calls
                         }
-                    }
+                    },
                 }
             })
             .collect();
 
         ImplTokenSets {
-            // No namespaces can be declared directly on the element enclosing
-            // an enum value, nor can it be provided with attribute fields, so
-            // the default `serialize_as_element()` implementation is
-            // sufficient.
+            // Namespaces are declared on the elements representing each
+            // variant rather than on the element enclosing an enum value, and
+            // the latter can't be provided with attribute fields, so the
+            // default `serialize_as_element()` implementation is sufficient.
             as_element_impl: TokenStream::default(),
 
             child_nodes_body: quote! {
@@ -259,6 +362,14 @@ pub(super) fn with_enum_variants(
                 }
             },
 
+            // Attributes are set on the elements representing each variant,
+            // so there are none to set on an enclosing element.
+            attributes_impl: TokenStream::default(),
+
+            // For the same reason, the enclosing element need not declare any
+            // namespaces.
+            declared_namespaces_impl: TokenStream::default(),
+
             // There is no clear text representation of an arbitrary enum
             // variant, so we cannot provide an `XmlSerializeAttr` derivation.
             as_attr_body: None,
@@ -280,13 +391,23 @@ struct VariantTokenSets {
 /// enum variant.
 fn generate_variant_token_sets(
     name_tokens: TokenStream,
-    namespace_attrs: &[XmlAttribute],
+    namespace_decls: &[NamespaceDeclTokens],
     fields: Vec<Field>,
 ) -> VariantTokenSets {
     let accessors: Vec<_> = fields
         .iter()
-        .map(|field| &field.accessor)
-        .cloned()
+        .map(|field| {
+            if !field.props.should_skip {
+                return field.accessor.clone();
+            }
+
+            // Skipped fields aren't used in serialization, so we avoid binding
+            // them in order to prevent unused variable warnings.
+            match &field.kind {
+                FieldKind::Named { ident, .. } => quote!(#ident: _),
+                FieldKind::Unnamed { .. } => quote!(_),
+            }
+        })
         .collect();
 
     let Fields {
@@ -294,14 +415,29 @@ fn generate_variant_token_sets(
         child_fields,
     } = partition_fields(fields);
 
+    let namespaces = field_namespaces(attr_fields.iter().chain(&child_fields));
+    let content_types = content_node_field_types(&child_fields);
+
+    let attr_calls = generate_attribute_field_calls(&attr_fields, Some(&name_tokens));
+    let content_attr_calls = generate_content_attribute_calls(&child_fields, Some(&name_tokens));
+
     let content_calls = if !child_fields.is_empty() {
-        Some(generate_field_content_node_calls(child_fields))
+        Some(generate_field_content_node_calls(
+            child_fields,
+            Some(&name_tokens),
+        ))
     } else {
         None
     };
 
-    let variant_body =
-        generate_xml_tag_calls(name_tokens, namespace_attrs, &attr_fields, content_calls);
+    let variant_body = generate_xml_tag_calls(
+        name_tokens,
+        namespace_decls,
+        &content_types,
+        &namespaces,
+        quote!(#attr_calls #content_attr_calls),
+        content_calls,
+    );
 
     VariantTokenSets {
         accessors,
@@ -309,51 +445,244 @@ fn generate_variant_token_sets(
     }
 }
 
-/// Divides the fields of a struct or enum variant into those which will be
-/// represented as attributes and those which will be represented as child nodes.
-fn partition_fields(fields: Vec<Field>) -> Fields {
-    let (attr_fields, child_fields) = fields
-        .into_iter()
-        .partition(|field| matches!(field.props.repr, FieldRepr::Attribute));
+/// Converts namespace declaration properties into tokens for use in generating
+/// declarations, with any default namespace first.
+fn namespace_decl_tokens(
+    default_ns_name: Option<TokenStream>,
+    ns_decls: Vec<NamespaceDecl>,
+) -> Vec<NamespaceDeclTokens> {
+    // The terminology is a little confusing here. In terms of the XML spec,
+    // the "name" of a namespace is the (usually) URI used as the _value_ of
+    // the namespace declaration attribute.
+    let default_ns_decl = default_ns_name.map(|ns_name| NamespaceDeclTokens {
+        prefix: quote!(::core::option::Option::None),
+        name: ns_name,
+    });
 
-    Fields {
-        attr_fields,
-        child_fields,
-    }
+    let ns_decls = ns_decls.into_iter().map(|ns_decl| {
+        let prefix = ns_decl.prefix;
+
+        NamespaceDeclTokens {
+            prefix: quote!(::core::option::Option::Some(#prefix)),
+            name: ns_decl.name,
+        }
+    });
+
+    default_ns_decl.into_iter().chain(ns_decls).collect()
 }
 
-/// Generates tokens representing a call to add namespace attributes to an
-/// element.
-fn generate_namespace_attrs_call(namespace_attrs: &[XmlAttribute]) -> TokenStream {
-    if !namespace_attrs.is_empty() {
-        let namespace_attrs: Vec<_> = namespace_attrs
-            .iter()
-            .map(|XmlAttribute { name, value }| quote!((#name, #value)))
-            .collect();
+/// Combines the namespace declarations of an enum with those of one of its
+/// variants.
+///
+/// A declaration by the variant replaces any by the enum of the same prefix,
+/// or of the default namespace, so that the variant's element never carries
+/// two declarations of one prefix. Prefixes are compared as written.
+fn merge_namespace_decls(
+    type_decls: &[NamespaceDeclTokens],
+    variant_decls: Vec<NamespaceDeclTokens>,
+) -> Vec<NamespaceDeclTokens> {
+    let overridden: Vec<_> = variant_decls
+        .iter()
+        .map(|decl| decl.prefix.to_string())
+        .collect();
+
+    type_decls
+        .iter()
+        .filter(|decl| !overridden.contains(&decl.prefix.to_string()))
+        .cloned()
+        .chain(variant_decls)
+        .collect()
+}
 
+/// Generates tokens representing a call to add namespace declarations to an
+/// element's start tag.
+///
+/// Declarations already in scope from an enclosing element are omitted. The
+/// declarations required by the types in `content_types`, which are serialized
+/// as content nodes of the element, follow the element's own. Prefixes for the
+/// namespaces in which the element's fields are named are also declared if not
+/// already in scope, so that they can be shared by all of its fields.
+fn generate_namespace_decls_call(
+    namespace_decls: &[NamespaceDeclTokens],
+    content_types: &[TokenStream],
+    namespaces: &[TokenStream],
+) -> TokenStream {
+    let namespace_decls: Vec<_> = namespace_decls
+        .iter()
+        .map(|NamespaceDeclTokens { prefix, name }| quote!((#prefix, #name)))
+        .collect();
+
+    let declarations = if content_types.is_empty() {
+        quote!([#(#namespace_decls,)*])
+    } else {
+        // A default namespace declaration would change the namespace of the
+        // enclosing element and its unprefixed children, so only prefixed
+        // declarations are hoisted.
         quote! {
-            .with_attributes([
-                #(#namespace_attrs,)*
-            ])
+            ::core::iter::IntoIterator::into_iter([#(#namespace_decls,)*])
+                .chain(
+                    ::core::iter::IntoIterator::into_iter([
+                        #(<#content_types as ::xml_struct::XmlSerialize>::declared_namespaces(),)*
+                    ])
+                    .flatten()
+                    .filter(|(prefix, _)| prefix.is_some())
+                )
+        }
+    };
+
+    quote! {
+        writer.declare_namespaces(&mut start_tag, #declarations);
+
+        #(writer.declare_namespace(&mut start_tag, #namespaces);)*
+    }
+}
+
+/// Collects the types of fields serialized as content nodes without an element
+/// of their own, i.e. unnamed and flattened fields, so that the namespaces
+/// they require may be declared on the element enclosing them.
+///
+/// Fields serialized with custom functions or through a wrapper, e.g. as
+/// CDATA, are not represented by their type's implementation and so are not
+/// collected.
+fn content_node_field_types(fields: &[Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter(|field| is_content_node_field(field))
+        .map(|field| field.ty.to_token_stream())
+        .collect()
+}
+
+/// Determines whether a field is serialized as content nodes without an
+/// element of its own by its type's implementation.
+fn is_content_node_field(field: &Field) -> bool {
+    let is_content_only = match field.kind {
+        FieldKind::Named { .. } => {
+            matches!(field.props.repr, FieldRepr::Element) && field.props.should_flatten
+        }
+        FieldKind::Unnamed { .. } => true,
+    };
+
+    is_content_only
+        && field.props.serialize_with.is_none()
+        && !field.props.should_use_cdata
+        && field.props.map_repr.is_none()
+        && field.props.item_name.is_none()
+}
+
+/// Collects the distinct namespaces in which the given fields are named.
+///
+/// Namespaces are compared as written, so the same namespace given through
+/// different expressions is collected more than once; the prefix declared for
+/// the first is reused for the rest.
+fn field_namespaces<'a, I>(fields: I) -> Vec<TokenStream>
+where
+    I: IntoIterator<Item = &'a Field>,
+{
+    let mut namespaces: Vec<TokenStream> = Vec::new();
+    for ns_name in fields
+        .into_iter()
+        .filter_map(|field| field.props.ns_name.as_ref())
+    {
+        let ns_string = ns_name.to_string();
+        if !namespaces
+            .iter()
+            .any(|namespace| namespace.to_string() == ns_string)
+        {
+            namespaces.push(ns_name.clone());
         }
-    } else {
-        TokenStream::default()
     }
+
+    namespaces
 }
 
 /// Generates calls to serialize struct or enum fields as XML attributes.
-fn generate_attribute_field_calls(attr_fields: &[Field]) -> TokenStream {
+///
+/// If the attributes are set on an element written by the generated code
+/// itself, `element_name` is its name, used in describing the location of any
+/// error.
+fn generate_attribute_field_calls(
+    attr_fields: &[Field],
+    element_name: Option<&TokenStream>,
+) -> TokenStream {
+    let element_path = element_name.map(|name| quote!(.in_element(#name)));
+
     if !attr_fields.is_empty() {
         attr_fields
             .iter()
             .map(|field| {
-                let name = field_name_to_string_tokens(field);
+                // The name of an attribute in a namespace depends on the
+                // prefix bound to it on the start tag, so we bind it before
+                // borrowing the start tag for the attribute itself.
+                let (name_binding, name) = if field.props.ns_name.is_some() {
+                    let name = attribute_name_to_string_tokens(field);
+
+                    (
+                        Some(quote!(let attribute_name: &str = #name;)),
+                        quote!(attribute_name),
+                    )
+                } else {
+                    (None, field_name_to_string_tokens(field))
+                };
+
                 let accessor = &field.accessor;
                 let ty = &field.ty;
 
-                quote! {
-                    <#ty as ::xml_struct::XmlSerializeAttr>::serialize_as_attribute(&#accessor, &mut start_tag, #name);
-                }
+                let (call, path) = match &field.props.serialize_with {
+                    // Each entry of a map is added to the start tag as its own
+                    // attribute, which is named in any error.
+                    _ if matches!(field.props.map_repr, Some(MapRepr::Attributes)) => (
+                        quote! {
+                            ::xml_struct::MapAttributes::<#ty>::new(#accessor).serialize_as_attributes(&mut start_tag)
+                        },
+                        quote!(#element_path),
+                    ),
+
+                    // The entries of a list are joined into a single
+                    // attribute value.
+                    _ if field.props.list_separator.is_some() => {
+                        let separator = field.props.list_separator.as_deref();
+
+                        (
+                            quote! {
+                                ::xml_struct::XmlSerializeAttr::serialize_as_attribute(
+                                    &::xml_struct::AttrList::with_separator(#accessor, #separator),
+                                    &mut start_tag,
+                                    #name,
+                                )
+                            },
+                            quote!(.in_attribute(#name) #element_path),
+                        )
+                    }
+
+                    Some(hook) => {
+                        let function = hook.path_for("serialize_as_attribute");
+
+                        (
+                            quote!(#function(#accessor, &mut start_tag, #name)),
+                            quote!(.in_attribute(#name) #element_path),
+                        )
+                    }
+
+                    None => (
+                        quote! {
+                            <#ty as ::xml_struct::XmlSerializeAttr>::serialize_as_attribute(#accessor, &mut start_tag, #name)
+                        },
+                        quote!(.in_attribute(#name) #element_path),
+                    ),
+                };
+
+                let calls = propagate_with_context(field, call, path);
+                let calls = match name_binding {
+                    Some(name_binding) => quote! {
+                        {
+                            #name_binding
+                            #calls
+                        }
+                    },
+                    None => calls,
+                };
+
+                with_skip_condition(field, calls)
             })
             .collect()
     } else {
@@ -361,6 +690,37 @@ fn generate_attribute_field_calls(attr_fields: &[Field]) -> TokenStream {
     }
 }
 
+/// Generates calls to set the attributes of fields serialized as content nodes
+/// without an element of their own, i.e. unnamed and flattened fields, on the
+/// element enclosing them.
+///
+/// As for `generate_attribute_field_calls()`, `element_name` is the name of
+/// that element if it is written by the generated code itself.
+fn generate_content_attribute_calls(
+    child_fields: &[Field],
+    element_name: Option<&TokenStream>,
+) -> TokenStream {
+    let element_path = element_name.map(|name| quote!(.in_element(#name)));
+
+    child_fields
+        .iter()
+        .filter(|field| is_content_node_field(field))
+        .map(|field| {
+            let accessor = &field.accessor;
+            let ty = &field.ty;
+
+            let call = quote! {
+                <#ty as ::xml_struct::XmlSerialize>::serialize_attributes(#accessor, writer, &mut start_tag)
+            };
+
+            with_skip_condition(
+                field,
+                propagate_with_context(field, call, element_path.clone().unwrap_or_default()),
+            )
+        })
+        .collect()
+}
+
 /// Generates calls to add a new XML element to a document, including any
 /// necessary attributes and content nodes.
 ///
@@ -369,12 +729,13 @@ fn generate_attribute_field_calls(attr_fields: &[Field]) -> TokenStream {
 /// the writer by those calls.
 fn generate_xml_tag_calls(
     name_tokens: TokenStream,
-    namespace_attrs: &[XmlAttribute],
-    attr_fields: &[Field],
+    namespace_decls: &[NamespaceDeclTokens],
+    content_types: &[TokenStream],
+    namespaces: &[TokenStream],
+    attr_calls: TokenStream,
     content_calls: Option<TokenStream>,
 ) -> TokenStream {
-    let namespaces_call = generate_namespace_attrs_call(namespace_attrs);
-    let attr_calls = generate_attribute_field_calls(attr_fields);
+    let namespaces_call = generate_namespace_decls_call(namespace_decls, content_types, namespaces);
 
     let calls = if let Some(content_calls) = content_calls {
         // If the type has fields to serialize as child elements, wrap them
@@ -403,8 +764,8 @@ fn generate_xml_tag_calls(
     };
 
     quote! {
-        let mut start_tag = ::quick_xml::events::BytesStart::new(#name_tokens)
-            #namespaces_call;
+        let mut start_tag = ::quick_xml::events::BytesStart::new(#name_tokens);
+        #namespaces_call
 
         #attr_calls
 
@@ -413,7 +774,14 @@ fn generate_xml_tag_calls(
 }
 
 /// Generates calls to serialize the given fields as XML content nodes.
-fn generate_field_content_node_calls(child_fields: Vec<Field>) -> TokenStream {
+///
+/// If the calls are made within an element written by the generated code
+/// itself, `element_name` is its name, used in describing the location of any
+/// error.
+fn generate_field_content_node_calls(
+    child_fields: Vec<Field>,
+    element_name: Option<&TokenStream>,
+) -> TokenStream {
     child_fields
         .into_iter()
         .map(|field| {
@@ -421,187 +789,162 @@ fn generate_field_content_node_calls(child_fields: Vec<Field>) -> TokenStream {
                 panic!("attribute field passed to child node call generator");
             }
 
+            // Fields to be serialized as CDATA or as lists of named items are
+            // wrapped so that they are written via the wrapper's
+            // implementation rather than their own.
             let ty = &field.ty;
             let accessor = &field.accessor;
+            let (ty, value) = if field.props.should_use_cdata {
+                (
+                    quote!(::xml_struct::Cdata<&#ty>),
+                    quote!(&::xml_struct::Cdata(#accessor)),
+                )
+            } else if let Some(MapRepr::Entries) = field.props.map_repr {
+                let entry_name = field.props.item_name.as_deref().unwrap_or(DEFAULT_MAP_ENTRY_NAME);
+                let key_name = field.props.map_key_name.as_deref().unwrap_or(DEFAULT_MAP_KEY_NAME);
 
-            match field.kind {
-                FieldKind::Named(_) if !field.props.should_flatten => {
-                    let child_name = field_name_to_string_tokens(&field);
+                (
+                    quote!(::xml_struct::MapEntries<'_, #ty>),
+                    quote!(&::xml_struct::MapEntries::<#ty>::new(#accessor, #entry_name, #key_name)),
+                )
+            } else if let Some(item_name) = &field.props.item_name {
+                (
+                    quote!(::xml_struct::Items<'_, _>),
+                    quote!(&::xml_struct::Items::new(#item_name, #accessor)),
+                )
+            } else {
+                (ty.clone(), accessor.clone())
+            };
+
+            let call = match (&field.kind, &field.props.serialize_with) {
+                // Each entry of a repeated field is serialized as an element
+                // named for the field.
+                (FieldKind::Named { .. }, _) if field.props.should_repeat => {
+                    let call = match field_qualified_name_tokens(&field) {
+                        Some(qualified_name) => quote! {
+                            ::xml_struct::XmlSerialize::serialize_as_qualified_element(item, writer, #qualified_name)
+                        },
+                        None => {
+                            let child_name = field_name_to_string_tokens(&field);
+
+                            quote! {
+                                ::xml_struct::XmlSerialize::serialize_as_element(item, writer, #child_name)
+                            }
+                        }
+                    };
 
                     quote! {
-                        <#ty as ::xml_struct::XmlSerialize>::serialize_as_element(&#accessor, writer, #child_name)?;
+                        {
+                            let items: &#ty = #accessor;
+                            ::core::iter::IntoIterator::into_iter(items).try_for_each(|item| {
+                                #call
+                            })
+                        }
+                    }
+                }
+
+                (FieldKind::Named { .. }, hook)
+                    if matches!(field.props.repr, FieldRepr::Element)
+                        && !field.props.should_flatten =>
+                {
+                    let qualified_name = field_qualified_name_tokens(&field);
+                    match (hook, qualified_name) {
+                        // Implementations of `serialize_as_element()` name
+                        // their own element in any error, but we can't rely
+                        // on hooks to do the same.
+                        (Some(hook), qualified_name) => {
+                            let function = hook.path_for("serialize_as_element");
+                            let call = quote! {
+                                #function(#value, writer, child_name)
+                                    .map_err(|err| err.in_element(child_name))
+                            };
+
+                            // A prefix declared for a name in a namespace is
+                            // made on the element the hook writes.
+                            match qualified_name {
+                                Some(qualified_name) => quote! {
+                                    writer.with_element_name(#qualified_name, |writer, child_name| #call)
+                                },
+                                None => {
+                                    let child_name = field_name_to_string_tokens(&field);
+
+                                    quote! {
+                                        {
+                                            let child_name: &str = #child_name;
+                                            #call
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        (None, Some(qualified_name)) => quote! {
+                            <#ty as ::xml_struct::XmlSerialize>::serialize_as_qualified_element(#value, writer, #qualified_name)
+                        },
+                        (None, None) => {
+                            let child_name = field_name_to_string_tokens(&field);
+
+                            quote! {
+                                <#ty as ::xml_struct::XmlSerialize>::serialize_as_element(#value, writer, #child_name)
+                            }
+                        }
                     }
                 }
 
                 // If this is a tuple struct or the consumer has specifically
-                // requested a flat representation, serialize without a
-                // containing element.
-                _ => {
+                // requested a flat or text representation, serialize without
+                // a containing element.
+                (_, hook) => {
+                    let function = match hook {
+                        Some(hook) => hook.path_for("serialize_child_nodes"),
+                        None => quote!(<#ty as ::xml_struct::XmlSerialize>::serialize_child_nodes),
+                    };
+
                     quote! {
-                        <#ty as ::xml_struct::XmlSerialize>::serialize_child_nodes(&#accessor, writer)?;
+                        #function(#value, writer)
                     }
                 }
-            }
-        })
-        .collect()
-}
+            };
 
-/// Converts the name of a field to a string suitable for use as a tag name.
-///
-/// The identifier is stringified and converted to the desired case system. It
-/// will also generate code for concatenating the field name with any namespace
-/// prefix to be added.
-fn field_name_to_string_tokens(field: &Field) -> TokenStream {
-    match &field.kind {
-        FieldKind::Named(ident) => {
-            let name = ident.to_string();
-
-            let case_mapped = kebab_to_pascal(&name);
-
-            if let Some(prefix) = &field.props.namespace_prefix {
-                let string_with_colon = format!(":{case_mapped}");
-                generate_static_string_concat(prefix, Literal::string(&string_with_colon))
-            } else {
-                Literal::string(&case_mapped).into_token_stream()
-            }
-        }
+            let path = element_name.map(|name| quote!(.in_element(#name)));
 
-        FieldKind::Unnamed => panic!("cannot stringify unnamed field"),
-    }
-}
-
-/// Converts a kebab_case identifier string to PascalCase.
-fn kebab_to_pascal(kebab: &str) -> String {
-    let mut capitalize_next = true;
-
-    kebab
-        .chars()
-        .filter_map(|character| {
-            if character == '_' {
-                // Consume the underscore and capitalize the next character.
-                capitalize_next = true;
-
-                None
-            } else if capitalize_next {
-                capitalize_next = false;
-
-                // Rust supports non-ASCII identifiers, so this could
-                // technically fail, but this macro does not currently handle
-                // the general XML case, and so full Unicode case mapping is out
-                // of scope at present.
-                Some(character.to_ascii_uppercase())
-            } else {
-                Some(character)
-            }
+            with_skip_condition(&field, propagate_with_context(&field, call, path.unwrap_or_default()))
         })
         .collect()
 }
 
-#[derive(Debug)]
-/// A representation of an enum variant.
-pub(crate) struct Variant {
-    // The identifier for the variant.
-    pub ident: Ident,
-
-    // The form of the variant, along with any fields.
-    pub kind: VariantKind,
-}
-
-#[derive(Debug)]
-/// The form of an enum variant and its contained fields.
-pub(crate) enum VariantKind {
-    Struct(Vec<Field>),
-    Tuple(Vec<Field>),
-    Unit,
-}
-
-#[derive(Debug)]
-/// A representation of a struct or enum field.
-pub(crate) struct Field {
-    // The form of the field, along with any identifier.
-    pub kind: FieldKind,
-
-    // The type of the field.
-    pub ty: TokenStream,
+/// Wraps the calls to serialize a field in a check of the field's
+/// `skip_serializing_if` condition, if any.
+fn with_skip_condition(field: &Field, calls: TokenStream) -> TokenStream {
+    match &field.props.skip_serializing_if {
+        Some(predicate) => {
+            let accessor = &field.accessor;
 
-    // An expression which will access the value of the field.
-    pub accessor: TokenStream,
+            quote! {
+                if !#predicate(#accessor) {
+                    #calls
+                }
+            }
+        }
 
-    // Properties affecting the serialization of the field.
-    pub props: FieldProps,
+        None => calls,
+    }
 }
 
-#[derive(Debug)]
-/// A container for partitioned attribute and child element fields.
-struct Fields {
-    attr_fields: Vec<Field>,
-    child_fields: Vec<Field>,
-}
+/// Wraps a call which serializes a field so that any error it returns is
+/// propagated, annotated with the field and with the additional path segments
+/// given by `path`.
+fn propagate_with_context(field: &Field, call: TokenStream, path: TokenStream) -> TokenStream {
+    let annotate = field_error_context_tokens(field, path);
 
-#[derive(Debug)]
-/// The form of a field, whether named or unnamed.
-pub(crate) enum FieldKind {
-    Named(Ident),
-    Unnamed,
+    quote! {
+        #call.map_err(#annotate)?;
+    }
 }
 
-/// Tokens representing an XML attribute's name/value pair.
-pub(crate) struct XmlAttribute {
+/// Tokens representing a namespace declaration, i.e. an optional prefix and
+/// the namespace name to which it is bound.
+#[derive(Clone)]
+pub(crate) struct NamespaceDeclTokens {
+    prefix: TokenStream,
     name: TokenStream,
-    value: TokenStream,
-}
-
-/// Generates code for concatenating strings at compile-time.
-///
-/// This code allows for concatenating `const` string references and/or string
-/// literals with zero runtime cost.
-fn generate_static_string_concat<T, U>(a: T, b: U) -> TokenStream
-where
-    T: ToTokens,
-    U: ToTokens,
-{
-    quote!({
-        const LEN: usize = #a.len() + #b.len();
-
-        const fn copy_bytes_into(input: &[u8], mut output: [u8; LEN], offset: usize) -> [u8; LEN] {
-            // Copy the input byte-by-byte into the output buffer at the
-            // specified offset.
-            // NOTE: If/when `const_for` is stabilized, this can become a `for`
-            // loop. https://github.com/rust-lang/rust/issues/87575
-            let mut index = 0;
-            loop {
-                output[offset + index] = input[index];
-                index += 1;
-                if index == input.len() {
-                    break;
-                }
-            }
-
-            // We must return the buffer, as `const` functions cannot take a
-            // mutable reference, so it's moved into and out of scope.
-            output
-        }
-
-        const fn constcat(prefix: &'static str, value: &'static str) -> [u8; LEN] {
-            let mut output = [0u8; LEN];
-            output = copy_bytes_into(prefix.as_bytes(), output, 0);
-            output = copy_bytes_into(value.as_bytes(), output, prefix.len());
-
-            output
-        }
-
-        // As of writing this comment, Rust does not provide a standard macro
-        // for compile-time string concatenation, so we exploit the fact that
-        // `str::as_bytes()` and `std::str::from_utf8()` are `const`.
-        const BYTES: [u8; LEN] = constcat(#a, #b);
-        match std::str::from_utf8(&BYTES) {
-            Ok(value) => value,
-
-            // Given that both inputs to `constcat()` are Rust strings, they're
-            // guaranteed to be valid UTF-8. As such, directly concatenating
-            // them should create valid UTF-8 as well. If we hit this panic,
-            // it's probably a bug in one of the above functions.
-            Err(_) => panic!("Unable to statically concatenate strings"),
-        }
-    })
 }
@@ -0,0 +1,71 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Character and name productions of XML 1.0 and Namespaces in XML 1.0, along
+//! with the namespace names reserved by the latter.
+//!
+//! These are shared between the derive macro, which checks names given in
+//! attributes at compile time, and `xml_struct`, which checks serialized
+//! output at runtime.
+
+/// The namespace name to which the `xml` prefix is bound by definition.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The namespace name to which the `xmlns` prefix is bound by definition.
+pub const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+/// Determines whether a string matches the XML `Name` production.
+pub fn is_name(value: &str) -> bool {
+    let mut chars = value.chars();
+
+    match chars.next() {
+        Some(first) => is_name_start_char(first) && chars.all(is_name_char),
+        None => false,
+    }
+}
+
+/// Determines whether a string matches the `NCName` production of Namespaces
+/// in XML 1.0, i.e. it is an XML name containing no colons.
+pub fn is_ncname(value: &str) -> bool {
+    is_name(value) && !value.contains(':')
+}
+
+/// Determines whether a character matches the XML `Char` production.
+pub fn is_xml_char(c: char) -> bool {
+    matches!(c,
+        '\u{9}' | '\u{A}' | '\u{D}'
+        | '\u{20}'..='\u{D7FF}'
+        | '\u{E000}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{10FFFF}'
+    )
+}
+
+/// Determines whether a character matches the XML `NameStartChar` production.
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        ':' | 'A'..='Z' | '_' | 'a'..='z'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// Determines whether a character matches the XML `NameChar` production.
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{B7}'
+            | '\u{300}'..='\u{36F}'
+            | '\u{203F}'..='\u{2040}'
+        )
+}
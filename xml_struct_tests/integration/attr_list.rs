@@ -0,0 +1,104 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::{AttrList, XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(text)]
+enum Flag {
+    Read,
+    Flagged,
+    Draft,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Message {
+    #[xml_struct(attribute, list)]
+    flags: Vec<Flag>,
+
+    #[xml_struct(attribute, list = ",")]
+    categories: Vec<String>,
+
+    subject: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+struct Point {
+    #[xml_struct(list)]
+    coordinates: Vec<i32>,
+}
+
+#[test]
+fn attr_list_helper() {
+    #[derive(XmlSerialize)]
+    struct Wrapper<'a> {
+        #[xml_struct(attribute)]
+        class: AttrList<'a, Option<&'a str>>,
+    }
+
+    let classes = [Some("a"), None, Some("b&c")];
+    let content = Wrapper {
+        class: AttrList::new(&classes),
+    };
+
+    let actual = serialize_value_as_element(content, "div").expect("Failed to serialize list");
+
+    assert_eq!(
+        actual, r#"<div Class="a b&amp;c"/>"#,
+        "Entries should be separated by spaces, omitting those with no value"
+    );
+}
+
+#[test]
+fn struct_with_list_attributes() {
+    let content = Message {
+        flags: vec![Flag::Read, Flag::Flagged],
+        categories: vec![String::from("Work"), String::from("Red & Blue")],
+        subject: String::from("Hello"),
+    };
+
+    let expected = r#"<parent Flags="Read Flagged" Categories="Work,Red &amp; Blue"><Subject>Hello</Subject></parent>"#;
+
+    let actual = serialize_value_as_element(&content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "List entries should be joined with the separator"
+    );
+
+    let actual: Message = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(actual, content, "List attributes should round-trip");
+}
+
+#[test]
+fn list_attributes_from_element() {
+    let actual: Point = deserialize_value_from_str(r#"<Point Coordinates="  3	-4 5 "/>"#)
+        .expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        Point {
+            coordinates: vec![3, -4, 5]
+        },
+        "Entries should be separated by any whitespace"
+    );
+
+    let actual: Message = deserialize_value_from_str(
+        r#"<Message Flags="" Categories=""><Subject>Hello</Subject></Message>"#,
+    )
+    .expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        Message {
+            flags: vec![],
+            categories: vec![],
+            subject: String::from("Hello"),
+        },
+        "Empty attribute values should produce empty lists"
+    );
+}
@@ -0,0 +1,105 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::{Cdata, XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Body {
+    #[xml_struct(attribute)]
+    body_type: String,
+
+    #[xml_struct(text, cdata)]
+    content: String,
+}
+
+#[derive(XmlSerialize)]
+struct Script {
+    #[xml_struct(cdata)]
+    source: String,
+
+    #[xml_struct(flatten, cdata)]
+    comment: &'static str,
+}
+
+#[test]
+fn cdata_as_element() {
+    let actual = serialize_value_as_element(Cdata("<p>some & content</p>"), "Body")
+        .expect("Failed to serialize CDATA");
+    assert_eq!(
+        actual, "<Body><![CDATA[<p>some & content</p>]]></Body>",
+        "Serializing `Cdata` should result in an unescaped CDATA section"
+    );
+
+    let actual = serialize_value_as_element(Cdata(""), "Body").expect("Failed to serialize CDATA");
+    assert_eq!(
+        actual, "<Body><![CDATA[]]></Body>",
+        "Serializing empty `Cdata` should result in an empty CDATA section"
+    );
+}
+
+#[test]
+fn cdata_with_terminating_sequence() {
+    let content = "a]]>b]]>]]>";
+
+    let actual =
+        serialize_value_as_element(Cdata(content), "Body").expect("Failed to serialize CDATA");
+    assert_eq!(
+        actual, "<Body><![CDATA[a]]]]><![CDATA[>b]]]]><![CDATA[>]]]]><![CDATA[>]]></Body>",
+        "CDATA sections should be split within each terminating sequence"
+    );
+
+    let actual: Cdata<String> =
+        deserialize_value_from_str(&actual).expect("Failed to deserialize CDATA");
+    assert_eq!(
+        actual.into_inner(),
+        content,
+        "Split CDATA sections should be deserialized as the original text"
+    );
+}
+
+#[test]
+fn text_field_as_cdata() {
+    let content = Body {
+        body_type: String::from("HTML"),
+        content: String::from("<p>some text</p>"),
+    };
+
+    let expected = r#"<parent BodyType="HTML"><![CDATA[<p>some text</p>]]></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Text field marked `cdata` should be serialized as a CDATA section"
+    );
+
+    let actual: Body = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        Body {
+            body_type: String::from("HTML"),
+            content: String::from("<p>some text</p>"),
+        },
+        "Text field marked `cdata` should be deserialized from a CDATA section"
+    );
+}
+
+#[test]
+fn element_fields_as_cdata() {
+    let content = Script {
+        source: String::from("if (a < b && c) {}"),
+        comment: "x]]>y",
+    };
+
+    let expected = "<parent><Source><![CDATA[if (a < b && c) {}]]></Source><![CDATA[x]]]]><![CDATA[>y]]></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Element and flattened fields marked `cdata` should be serialized as CDATA sections"
+    );
+}
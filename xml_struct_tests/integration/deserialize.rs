@@ -0,0 +1,405 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use quick_xml::{events::Event, Reader};
+use xml_struct::{Error, XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element, TestError};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(default_ns = "http://foo.example/this_ns", ns = ("other_ns", "http://bar.example/other_ns"))]
+struct Struct {
+    #[xml_struct(attribute)]
+    str_attr: String,
+
+    #[xml_struct(attribute, ns_prefix = "other_ns")]
+    optional_attr: Option<u32>,
+
+    child: ChildStruct,
+
+    #[xml_struct(flatten)]
+    flattened: TupleStruct,
+
+    #[xml_struct(ns_prefix = "other_ns")]
+    more_complex_field_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct ChildStruct {
+    text_enum: TextEnum,
+    mixed_enum: MixedEnum,
+    unit: UnitStruct,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct TupleStruct(MixedEnum, MixedEnum);
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct UnitStruct;
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(text)]
+enum TextEnum {
+    A,
+    Two,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(variant_ns_prefix = "other_ns")]
+enum MixedEnum {
+    UnitVariant,
+    StructVariant {
+        #[xml_struct(attribute)]
+        attr_field: TextEnum,
+
+        numbers: Vec<i64>,
+    },
+    Flag(bool),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+enum EnumWithAttributesByDefault {
+    Variant {
+        id: String,
+        change_key: Option<String>,
+
+        #[xml_struct(element)]
+        child: String,
+    },
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(deny_unknown_elements)]
+struct StrictContent {
+    item: String,
+
+    #[xml_struct(flatten)]
+    flattened: FlattenedContent,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(deny_unknown_elements)]
+struct FlattenedContent {
+    other: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(deny_unknown_elements)]
+enum StrictVariants {
+    Variant { item: String },
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct WithFlattenedAttributes {
+    #[xml_struct(attribute)]
+    id: String,
+
+    #[xml_struct(flatten)]
+    flattened: FlattenedAttributes,
+
+    #[xml_struct(flatten)]
+    optional: Option<FlattenedAttributes>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct FlattenedAttributes {
+    #[xml_struct(attribute)]
+    change_key: String,
+
+    subject: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+enum VariantWithFlattenedAttributes {
+    Item(FlattenedAttributes),
+}
+
+fn sample_struct() -> Struct {
+    Struct {
+        str_attr: String::from("arbitrary <text>"),
+        optional_attr: Some(17),
+        child: ChildStruct {
+            text_enum: TextEnum::Two,
+            mixed_enum: MixedEnum::StructVariant {
+                attr_field: TextEnum::A,
+                numbers: vec![-3],
+            },
+            unit: UnitStruct,
+        },
+        flattened: TupleStruct(MixedEnum::UnitVariant, MixedEnum::Flag(true)),
+        more_complex_field_name: None,
+    }
+}
+
+#[test]
+fn round_trip() {
+    let expected = sample_struct();
+
+    let xml =
+        serialize_value_as_element(sample_struct(), "parent").expect("Failed to serialize struct");
+    let actual: Struct = deserialize_value_from_str(&xml).expect("Failed to deserialize struct");
+
+    assert_eq!(
+        actual, expected,
+        "Deserializing serialized output should produce the original value"
+    );
+}
+
+#[test]
+fn flattened_attributes_round_trip() {
+    let content = WithFlattenedAttributes {
+        id: String::from("abc"),
+        flattened: FlattenedAttributes {
+            change_key: String::from("def"),
+            subject: String::from("Hello"),
+        },
+        optional: None,
+    };
+
+    let xml = serialize_value_as_element(&content, "parent").expect("Failed to serialize struct");
+
+    assert_eq!(
+        xml, r#"<parent Id="abc" ChangeKey="def"><Subject>Hello</Subject></parent>"#,
+        "Attributes of flattened fields should be set on the enclosing element"
+    );
+
+    let actual: WithFlattenedAttributes =
+        deserialize_value_from_str(&xml).expect("Failed to deserialize struct");
+
+    assert_eq!(
+        actual, content,
+        "Attributes of flattened fields should be read from the enclosing element"
+    );
+
+    let content = VariantWithFlattenedAttributes::Item(FlattenedAttributes {
+        change_key: String::from("def"),
+        subject: String::from("Hello"),
+    });
+
+    let xml = serialize_value_as_element(&content, "parent").expect("Failed to serialize enum");
+
+    assert_eq!(
+        xml, r#"<parent><Item ChangeKey="def"><Subject>Hello</Subject></Item></parent>"#,
+        "Attributes of unnamed variant fields should be set on the variant's element"
+    );
+
+    let actual: VariantWithFlattenedAttributes =
+        deserialize_value_from_str(&xml).expect("Failed to deserialize enum");
+
+    assert_eq!(
+        actual, content,
+        "Attributes of unnamed variant fields should be read from the variant's element"
+    );
+}
+
+#[test]
+fn enum_with_attributes_by_default() {
+    let expected = EnumWithAttributesByDefault::Variant {
+        id: String::from("abc"),
+        change_key: None,
+        child: String::from("some text"),
+    };
+
+    let xml = serialize_value_as_element(
+        EnumWithAttributesByDefault::Variant {
+            id: String::from("abc"),
+            change_key: None,
+            child: String::from("some text"),
+        },
+        "parent",
+    )
+    .expect("Failed to serialize enum");
+
+    assert_eq!(
+        xml, r#"<parent><Variant Id="abc"><Child>some text</Child></Variant></parent>"#,
+        "Unmarked variant fields should be serialized as attributes"
+    );
+
+    let actual: EnumWithAttributesByDefault =
+        deserialize_value_from_str(&xml).expect("Failed to deserialize enum");
+
+    assert_eq!(
+        actual, expected,
+        "Unmarked variant fields should be deserialized from attributes"
+    );
+}
+
+#[test]
+fn struct_from_indented_document() {
+    let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- comments and whitespace between elements are ignored -->
+<parent xmlns="http://foo.example/this_ns" xmlns:other_ns="http://bar.example/other_ns" StrAttr="arbitrary &lt;text&gt;" other_ns:OptionalAttr="17">
+    <Child>
+        <TextEnum> Two </TextEnum>
+        <MixedEnum>
+            <other_ns:StructVariant AttrField="A">
+                <Numbers>-3</Numbers>
+            </other_ns:StructVariant>
+        </MixedEnum>
+        <Unit/>
+    </Child>
+    <other_ns:UnitVariant/>
+    <other_ns:Flag>true</other_ns:Flag>
+</parent>"#;
+
+    let expected = sample_struct();
+
+    let actual: Struct = deserialize_value_from_str(xml).expect("Failed to deserialize struct");
+
+    assert_eq!(
+        actual, expected,
+        "Indentation, comments, and declarations should not affect deserialization"
+    );
+}
+
+#[test]
+fn optional_fields_when_present() {
+    let xml = r#"<parent StrAttr=""><Child><TextEnum>A</TextEnum><MixedEnum><other_ns:UnitVariant/></MixedEnum><Unit/></Child><other_ns:UnitVariant/><other_ns:UnitVariant/><other_ns:MoreComplexFieldName>some text</other_ns:MoreComplexFieldName></parent>"#;
+
+    let actual: Struct = deserialize_value_from_str(xml).expect("Failed to deserialize struct");
+
+    assert_eq!(
+        actual.optional_attr, None,
+        "Missing attribute should be `None`"
+    );
+    assert_eq!(
+        actual.more_complex_field_name,
+        Some(String::from("some text")),
+        "Present optional element should be `Some`"
+    );
+}
+
+#[test]
+fn missing_element() {
+    let xml = r#"<parent StrAttr=""><other_ns:UnitVariant/><other_ns:UnitVariant/></parent>"#;
+
+    let actual: Result<Struct, _> = deserialize_value_from_str(xml);
+
+    assert!(
+        actual.is_err(),
+        "Missing required element should produce an error"
+    );
+}
+
+#[test]
+fn missing_attribute() {
+    let xml = r#"<parent><Child><TextEnum>A</TextEnum><MixedEnum><other_ns:UnitVariant/></MixedEnum><Unit/></Child><other_ns:UnitVariant/><other_ns:UnitVariant/></parent>"#;
+
+    let actual: Result<Struct, _> = deserialize_value_from_str(xml);
+
+    assert!(
+        actual.is_err(),
+        "Missing required attribute should produce an error"
+    );
+}
+
+#[test]
+fn unrecognized_variant() {
+    let actual: Result<MixedEnum, _> =
+        deserialize_value_from_str("<parent><UnitVariant/></parent>");
+    assert!(
+        actual.is_err(),
+        "Variant without expected namespace prefix should produce an error"
+    );
+
+    let actual: Result<TextEnum, _> = deserialize_value_from_str("<parent>Three</parent>");
+    assert!(
+        actual.is_err(),
+        "Unrecognized text enum value should produce an error"
+    );
+}
+
+#[test]
+fn children_read_one_at_a_time() {
+    let mut reader = Reader::from_str(r#"<Root><Unit/><Unit></Unit></Root>"#);
+
+    assert!(
+        matches!(reader.read_event(), Ok(Event::Start(_))),
+        "Document should begin with the parent's start tag"
+    );
+
+    for _ in 0..2 {
+        let actual =
+            UnitStruct::deserialize_from_reader(&mut reader).expect("Failed to read child");
+
+        assert_eq!(actual, UnitStruct, "Each child should be read in turn");
+    }
+
+    let actual = UnitStruct::deserialize_from_reader(&mut reader);
+
+    assert!(
+        matches!(actual, Err(Error::UnexpectedStructure(_))),
+        "Reaching the parent's end tag should produce an error, got {actual:?}"
+    );
+}
+
+#[test]
+fn remaining_elements_ignored_by_default() {
+    let actual: ChildStruct = deserialize_value_from_str(
+        r#"<parent><TextEnum>A</TextEnum><MixedEnum><other_ns:UnitVariant/></MixedEnum><Unit/><Unit/></parent>"#,
+    )
+    .expect("Failed to deserialize struct");
+
+    assert_eq!(
+        actual.text_enum,
+        TextEnum::A,
+        "Elements remaining after all fields are read should be ignored"
+    );
+}
+
+#[test]
+fn remaining_elements_denied() {
+    let actual: StrictContent =
+        deserialize_value_from_str(r#"<parent><Item>a</Item><Other>b</Other></parent>"#)
+            .expect("Failed to deserialize struct");
+
+    assert_eq!(
+        actual,
+        StrictContent {
+            item: String::from("a"),
+            flattened: FlattenedContent {
+                other: String::from("b"),
+            },
+        },
+        "Content nodes read by a flattened field should not be treated as remaining"
+    );
+
+    let actual: Result<StrictContent, _> = deserialize_value_from_str(
+        r#"<parent><Item>a</Item><Other>b</Other><Other>c</Other></parent>"#,
+    );
+
+    assert!(
+        matches!(
+            actual,
+            Err(TestError::XmlStruct(ref err)) if matches!(err.root_cause(), Error::UnexpectedStructure(_))
+        ),
+        "Duplicate element for a field which isn't repeated should produce an error, got {actual:?}"
+    );
+
+    let actual: Result<StrictContent, _> =
+        deserialize_value_from_str(r#"<parent><Item>a</Item><Other>b</Other><Unknown/></parent>"#);
+
+    assert!(
+        matches!(
+            actual,
+            Err(TestError::XmlStruct(ref err)) if matches!(err.root_cause(), Error::UnexpectedStructure(_))
+        ),
+        "Element not matching any field should produce an error, got {actual:?}"
+    );
+
+    let actual: Result<StrictVariants, _> = deserialize_value_from_str(
+        r#"<parent><Variant><Item>a</Item><Item>b</Item></Variant></parent>"#,
+    );
+    let err = match actual {
+        Err(TestError::XmlStruct(err)) => err,
+        actual => panic!("Duplicate element in variant should produce an error, got {actual:?}"),
+    };
+
+    assert_eq!(
+        err.context().map(|context| context.path()),
+        Some(String::from("/parent/Variant")),
+        "Error should name the variant's element"
+    );
+    assert!(matches!(err.root_cause(), Error::UnexpectedStructure(_)));
+}
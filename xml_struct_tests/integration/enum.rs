@@ -2,8 +2,10 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use xml_struct::XmlSerialize;
-use xml_struct_tests::{serialize_value_as_element, serialize_value_children};
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{
+    deserialize_value_from_str, serialize_value_as_element, serialize_value_children,
+};
 
 #[derive(XmlSerialize)]
 enum MixedEnum {
@@ -42,6 +44,26 @@ enum EnumWithNamespacePrefix {
     SomeValue,
 }
 
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(ns = ("t", "http://types.example/"))]
+enum EnumWithVariantNamespaces {
+    #[xml_struct(ns_prefix = "t")]
+    Message { subject: String },
+
+    #[xml_struct(
+        ns_prefix = "m",
+        ns = ("m", "http://messages.example/"),
+        ns = ("t", "http://other.example/")
+    )]
+    ResponseMessage { response_code: String },
+
+    #[xml_struct(default_ns = "http://messages.example/")]
+    Unprefixed,
+
+    #[xml_struct(ns = "http://messages.example/")]
+    Chosen,
+}
+
 #[test]
 fn mixed_enum_unit_variant() {
     let content = MixedEnum::UnitVariant;
@@ -250,3 +272,94 @@ fn enum_with_namespace_prefix_as_element() {
         "Enum variants should be serialized with specified prefix"
     );
 }
+
+#[test]
+fn enum_with_variant_namespaces() {
+    let content = EnumWithVariantNamespaces::Message {
+        subject: String::from("Hello"),
+    };
+
+    let expected =
+        r#"<t:Message xmlns:t="http://types.example/"><Subject>Hello</Subject></t:Message>"#;
+    let actual = serialize_value_children(content).expect("Failed to serialize enum value");
+
+    assert_eq!(
+        actual, expected,
+        "Variant should be serialized with its own prefix"
+    );
+
+    let content = EnumWithVariantNamespaces::ResponseMessage {
+        response_code: String::from("NoError"),
+    };
+
+    let expected = r#"<m:ResponseMessage xmlns:m="http://messages.example/" xmlns:t="http://other.example/"><ResponseCode>NoError</ResponseCode></m:ResponseMessage>"#;
+    let actual = serialize_value_children(content).expect("Failed to serialize enum value");
+
+    assert_eq!(
+        actual, expected,
+        "Variant declarations should replace those of the enum with the same prefix"
+    );
+
+    let content = EnumWithVariantNamespaces::Unprefixed;
+
+    let expected =
+        r#"<Unprefixed xmlns:t="http://types.example/" xmlns="http://messages.example/"/>"#;
+    let actual = serialize_value_children(content).expect("Failed to serialize enum value");
+
+    assert_eq!(
+        actual, expected,
+        "Variant should declare its own default namespace"
+    );
+
+    let content = EnumWithVariantNamespaces::Chosen;
+
+    let expected =
+        r#"<ns0:Chosen xmlns:t="http://types.example/" xmlns:ns0="http://messages.example/"/>"#;
+    let actual = serialize_value_children(content).expect("Failed to serialize enum value");
+
+    assert_eq!(
+        actual, expected,
+        "Variant should be named in its own namespace"
+    );
+}
+
+#[test]
+fn enum_with_variant_namespaces_as_element() {
+    let content = EnumWithVariantNamespaces::ResponseMessage {
+        response_code: String::from("NoError"),
+    };
+
+    let expected = r#"<Items><m:ResponseMessage xmlns:m="http://messages.example/" xmlns:t="http://other.example/"><ResponseCode>NoError</ResponseCode></m:ResponseMessage></Items>"#;
+    let actual =
+        serialize_value_as_element(content, "Items").expect("Failed to serialize enum value");
+
+    assert_eq!(
+        actual, expected,
+        "Namespaces should be declared on the variant's element"
+    );
+}
+
+#[test]
+fn enum_with_variant_namespaces_round_trip() {
+    let content = EnumWithVariantNamespaces::Message {
+        subject: String::from("Hello"),
+    };
+
+    let actual: EnumWithVariantNamespaces = deserialize_value_from_str(
+        r#"<Items><t:Message xmlns:t="http://types.example/"><Subject>Hello</Subject></t:Message></Items>"#,
+    )
+    .expect("Failed to deserialize enum value");
+
+    assert_eq!(actual, content, "Variant should be matched by its prefix");
+
+    let actual: EnumWithVariantNamespaces = deserialize_value_from_str(
+        r#"<Items><msg:Chosen xmlns:msg="http://messages.example/"/></Items>"#,
+    )
+    .expect("Failed to deserialize enum value");
+
+    assert_eq!(
+        actual,
+        EnumWithVariantNamespaces::Chosen,
+        "Variant should be matched by its namespace"
+    );
+}
@@ -0,0 +1,184 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use quick_xml::events::BytesStart;
+use xml_struct::{Error, XmlDeserialize, XmlSerialize, XmlSerializeAttr};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element, TestError};
+
+#[derive(XmlSerialize)]
+struct CreateItem {
+    #[xml_struct(item = "Message")]
+    items: Vec<Message>,
+}
+
+#[derive(XmlSerialize)]
+struct Message {
+    #[xml_struct(attribute)]
+    id: Checked,
+
+    body: Checked,
+}
+
+#[derive(XmlSerialize)]
+enum Body {
+    Html(Checked),
+}
+
+/// A value which fails to serialize when it's empty.
+struct Checked(&'static str);
+
+impl XmlSerialize for Checked {
+    fn serialize_child_nodes<W>(
+        &self,
+        writer: &mut xml_struct::XmlWriter<W>,
+    ) -> Result<(), xml_struct::Error>
+    where
+        W: std::io::Write,
+    {
+        self.check()?;
+
+        self.0.serialize_child_nodes(writer)
+    }
+}
+
+impl XmlSerializeAttr for Checked {
+    fn serialize_as_attribute(&self, start_tag: &mut BytesStart, name: &str) -> Result<(), Error> {
+        self.check()?;
+
+        self.0.serialize_as_attribute(start_tag, name)
+    }
+}
+
+impl Checked {
+    fn check(&self) -> Result<(), Error> {
+        if self.0.is_empty() {
+            Err(Error::Value(anyhow::anyhow!("value must not be empty")))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+struct GetFolder {
+    folder_ids: Vec<FolderId>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+enum FolderId {
+    DistinguishedFolderId {
+        #[xml_struct(attribute)]
+        id: String,
+
+        change_key: u32,
+    },
+}
+
+/// Extracts the error from the result of serializing or deserializing a value.
+fn unwrap_error<T>(result: Result<T, TestError>) -> Error
+where
+    T: std::fmt::Debug,
+{
+    match result.expect_err("Operation should fail") {
+        TestError::XmlStruct(err) => err,
+        err => panic!("Unexpected error {err:?}"),
+    }
+}
+
+#[test]
+fn serialize_error_in_nested_element() {
+    let content = CreateItem {
+        items: vec![
+            Message {
+                id: Checked("abc"),
+                body: Checked("hello"),
+            },
+            Message {
+                id: Checked("def"),
+                body: Checked(""),
+            },
+        ],
+    };
+
+    let err = unwrap_error(serialize_value_as_element(content, "CreateItem"));
+    let context = err.context().expect("Error should have context");
+
+    assert_eq!(context.path(), "/CreateItem/Items/Message/Body");
+    assert_eq!(context.field_name(), Some("body"));
+    assert!(
+        context
+            .type_name()
+            .is_some_and(|name| name.ends_with("Message")),
+        "Context should name the innermost type, got {:?}",
+        context.type_name()
+    );
+    assert!(matches!(err.root_cause(), Error::Value(_)));
+}
+
+#[test]
+fn serialize_error_in_attribute() {
+    let content = Message {
+        id: Checked(""),
+        body: Checked("hello"),
+    };
+
+    let err = unwrap_error(serialize_value_as_element(content, "Message"));
+    let context = err.context().expect("Error should have context");
+
+    assert_eq!(context.path(), "/Message/@Id");
+    assert_eq!(context.field_name(), Some("id"));
+}
+
+#[test]
+fn serialize_error_in_variant() {
+    let content = Body::Html(Checked(""));
+
+    let err = unwrap_error(serialize_value_as_element(content, "Body"));
+    let context = err.context().expect("Error should have context");
+
+    assert_eq!(context.path(), "/Body/Html");
+    assert_eq!(
+        context.field_name(),
+        Some("0"),
+        "Unnamed fields should be named by position"
+    );
+}
+
+#[test]
+fn deserialize_error_in_nested_element() {
+    let err = unwrap_error(deserialize_value_from_str::<GetFolder>(
+        r#"<GetFolder><FolderIds><DistinguishedFolderId Id="inbox"><ChangeKey>five</ChangeKey></DistinguishedFolderId></FolderIds></GetFolder>"#,
+    ));
+    let context = err.context().expect("Error should have context");
+
+    assert_eq!(
+        context.path(),
+        "/GetFolder/FolderIds/DistinguishedFolderId/ChangeKey"
+    );
+    assert_eq!(context.field_name(), Some("change_key"));
+    assert!(matches!(err.root_cause(), Error::Value(_)));
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "error at `/GetFolder/FolderIds/DistinguishedFolderId/ChangeKey` (field `change_key` of `{}`)",
+            context.type_name().unwrap()
+        ),
+    );
+}
+
+#[test]
+fn deserialize_error_in_attribute() {
+    let err = unwrap_error(deserialize_value_from_str::<GetFolder>(
+        r#"<GetFolder><FolderIds><DistinguishedFolderId><ChangeKey>5</ChangeKey></DistinguishedFolderId></FolderIds></GetFolder>"#,
+    ));
+    let context = err.context().expect("Error should have context");
+
+    assert_eq!(
+        context.path(),
+        "/GetFolder/FolderIds/DistinguishedFolderId/@Id"
+    );
+    assert_eq!(context.field_name(), Some("id"));
+    assert!(matches!(err.root_cause(), Error::UnexpectedStructure(_)));
+}
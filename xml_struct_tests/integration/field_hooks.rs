@@ -0,0 +1,341 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::time::Duration;
+
+use quick_xml::events::BytesStart;
+use xml_struct::{
+    Content, Element, Error, XmlDeserialize, XmlSerialize, XmlSerializeAttr, XmlWriter,
+};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element, TestError};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct StructWithHooks {
+    #[xml_struct(attribute, with = "seconds")]
+    timeout_attr: Duration,
+
+    #[xml_struct(with = "seconds")]
+    timeout: Duration,
+
+    #[xml_struct(flatten, with = "seconds")]
+    flattened_timeout: Duration,
+
+    #[xml_struct(serialize_with = "write_uppercase")]
+    shouted: String,
+
+    #[xml_struct(attribute, serialize_with = "write_uppercase_attr")]
+    shouted_attr: String,
+}
+
+#[derive(XmlSerialize)]
+struct StructWithFallibleHook {
+    #[xml_struct(attribute, serialize_with = "write_nonempty_attr")]
+    name: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+enum EnumWithHooks {
+    Timeout(#[xml_struct(with = "seconds")] Duration),
+    Interval {
+        #[xml_struct(
+            serialize_with = "seconds::serialize_as_element",
+            deserialize_with = "seconds::deserialize_as_element"
+        )]
+        length: Duration,
+    },
+}
+
+#[derive(XmlSerialize)]
+enum EnumWithGenericHooks {
+    Counts {
+        #[xml_struct(attribute, with = "doubled")]
+        attr: u32,
+
+        #[xml_struct(with = "doubled")]
+        child: u16,
+    },
+    Count(#[xml_struct(with = "doubled")] u8),
+}
+
+#[derive(XmlSerialize)]
+enum EnumWithNamespacedHooks {
+    #[xml_struct(ns = "http://messages.example/")]
+    Timeout(#[xml_struct(with = "seconds")] Duration),
+
+    #[xml_struct(ns = "http://messages.example/")]
+    Interval {
+        #[xml_struct(ns = "http://types.example/", with = "seconds")]
+        length: Duration,
+    },
+}
+
+#[derive(XmlSerialize)]
+struct StructWithEnumHooks {
+    #[xml_struct(flatten, serialize_with = "write_children")]
+    flattened: EnumWithNamespacedHooks,
+
+    #[xml_struct(serialize_with = "write_element")]
+    wrapped: EnumWithNamespacedHooks,
+}
+
+/// Serializes an unsigned integer as twice its value.
+mod doubled {
+    use super::*;
+
+    pub fn serialize_as_element<T, W>(
+        value: &T,
+        writer: &mut XmlWriter<W>,
+        name: &str,
+    ) -> Result<(), Error>
+    where
+        T: Copy + Into<u64>,
+        W: std::io::Write,
+    {
+        ((*value).into() * 2).serialize_as_element(writer, name)
+    }
+
+    pub fn serialize_child_nodes<T, W>(value: &T, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        T: Copy + Into<u64>,
+        W: std::io::Write,
+    {
+        ((*value).into() * 2).serialize_child_nodes(writer)
+    }
+
+    pub fn serialize_as_attribute<T>(
+        value: &T,
+        start_tag: &mut BytesStart,
+        name: &str,
+    ) -> Result<(), Error>
+    where
+        T: Copy + Into<u64>,
+    {
+        ((*value).into() * 2).serialize_as_attribute(start_tag, name)
+    }
+}
+
+/// Serializes a `Duration` as a whole number of seconds.
+mod seconds {
+    use super::*;
+
+    pub fn serialize_as_element<W: std::io::Write>(
+        value: &Duration,
+        writer: &mut XmlWriter<W>,
+        name: &str,
+    ) -> Result<(), Error> {
+        value.as_secs().serialize_as_element(writer, name)
+    }
+
+    pub fn serialize_child_nodes<W: std::io::Write>(
+        value: &Duration,
+        writer: &mut XmlWriter<W>,
+    ) -> Result<(), Error> {
+        value.as_secs().serialize_child_nodes(writer)
+    }
+
+    pub fn serialize_as_attribute(
+        value: &Duration,
+        start_tag: &mut BytesStart,
+        name: &str,
+    ) -> Result<(), Error> {
+        value.as_secs().serialize_as_attribute(start_tag, name)
+    }
+
+    pub fn deserialize_as_element(
+        content: &mut Content<'_>,
+        name: &str,
+    ) -> Result<Duration, Error> {
+        u64::deserialize_as_element(content, name).map(Duration::from_secs)
+    }
+
+    pub fn deserialize_child_nodes(content: &mut Content<'_>) -> Result<Duration, Error> {
+        u64::deserialize_child_nodes(content).map(Duration::from_secs)
+    }
+
+    pub fn deserialize_from_attribute(element: &Element, name: &str) -> Result<Duration, Error> {
+        <u64 as xml_struct::XmlDeserializeAttr>::deserialize_from_attribute(element, name)
+            .map(Duration::from_secs)
+    }
+}
+
+fn write_children<T, W>(value: &T, writer: &mut XmlWriter<W>) -> Result<(), Error>
+where
+    T: XmlSerialize,
+    W: std::io::Write,
+{
+    value.serialize_child_nodes(writer)
+}
+
+fn write_element<T, W>(value: &T, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
+where
+    T: XmlSerialize,
+    W: std::io::Write,
+{
+    value.serialize_as_element(writer, name)
+}
+
+fn write_uppercase<W: std::io::Write>(
+    value: &str,
+    writer: &mut XmlWriter<W>,
+    name: &str,
+) -> Result<(), Error> {
+    value.to_uppercase().serialize_as_element(writer, name)
+}
+
+fn write_uppercase_attr(value: &str, start_tag: &mut BytesStart, name: &str) -> Result<(), Error> {
+    value.to_uppercase().serialize_as_attribute(start_tag, name)
+}
+
+fn write_nonempty_attr(value: &str, start_tag: &mut BytesStart, name: &str) -> Result<(), Error> {
+    if value.is_empty() {
+        return Err(Error::Value(anyhow::anyhow!("`{name}` must not be empty")));
+    }
+
+    value.serialize_as_attribute(start_tag, name)
+}
+
+#[test]
+fn struct_with_hooks() {
+    let content = StructWithHooks {
+        timeout_attr: Duration::from_secs(30),
+        timeout: Duration::from_secs(60),
+        flattened_timeout: Duration::from_secs(90),
+        shouted: String::from("hello"),
+        shouted_attr: String::from("world"),
+    };
+
+    let expected = r#"<parent TimeoutAttr="30" ShoutedAttr="WORLD"><Timeout>60</Timeout>90<Shouted>HELLO</Shouted></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Fields with hooks should be serialized using the provided functions"
+    );
+
+    let actual: StructWithHooks =
+        deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        StructWithHooks {
+            timeout_attr: Duration::from_secs(30),
+            timeout: Duration::from_secs(60),
+            flattened_timeout: Duration::from_secs(90),
+            shouted: String::from("HELLO"),
+            shouted_attr: String::from("WORLD"),
+        },
+        "Fields with hooks should be deserialized using the provided functions"
+    );
+}
+
+#[test]
+fn enum_with_hooks() {
+    let content = EnumWithHooks::Interval {
+        length: Duration::from_secs(5),
+    };
+
+    let expected = "<parent><Interval><Length>5</Length></Interval></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Variant fields with hooks should be serialized using the provided functions"
+    );
+
+    let actual: EnumWithHooks = deserialize_value_from_str(&actual).expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        EnumWithHooks::Interval {
+            length: Duration::from_secs(5)
+        },
+        "Variant fields with hooks should be deserialized using the provided functions"
+    );
+
+    let content = EnumWithHooks::Timeout(Duration::from_secs(7));
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, "<parent><Timeout>7</Timeout></parent>",
+        "Tuple variant fields with hooks should be serialized using the provided functions"
+    );
+
+    let actual: EnumWithHooks = deserialize_value_from_str(&actual).expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        EnumWithHooks::Timeout(Duration::from_secs(7)),
+        "Tuple variant fields with hooks should be deserialized using the provided functions"
+    );
+}
+
+#[test]
+fn enum_with_generic_hooks() {
+    let content = EnumWithGenericHooks::Counts { attr: 2, child: 3 };
+
+    let expected = r#"<parent><Counts Attr="4"><Child>6</Child></Counts></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Generic hooks should be given references to variant field values"
+    );
+
+    let content = EnumWithGenericHooks::Count(5);
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, "<parent><Count>10</Count></parent>",
+        "Generic hooks should be given references to tuple variant field values"
+    );
+}
+
+#[test]
+fn enum_with_namespaced_hooks() {
+    let content = StructWithEnumHooks {
+        flattened: EnumWithNamespacedHooks::Timeout(Duration::from_secs(7)),
+        wrapped: EnumWithNamespacedHooks::Interval {
+            length: Duration::from_secs(5),
+        },
+    };
+
+    let expected = r#"<parent><ns0:Timeout xmlns:ns0="http://messages.example/">7</ns0:Timeout><Wrapped><ns0:Interval xmlns:ns1="http://types.example/" xmlns:ns0="http://messages.example/"><ns1:Length>5</ns1:Length></ns0:Interval></Wrapped></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Variants in a namespace written through hooks should declare their prefix"
+    );
+}
+
+#[test]
+fn attribute_hook_error() {
+    let content = StructWithFallibleHook {
+        name: String::from("inbox"),
+    };
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(actual, r#"<parent Name="inbox"/>"#);
+
+    let content = StructWithFallibleHook {
+        name: String::new(),
+    };
+
+    let actual = serialize_value_as_element(content, "parent");
+
+    assert!(
+        matches!(
+            &actual,
+            Err(TestError::XmlStruct(err)) if matches!(err.root_cause(), Error::Value(_))
+        ),
+        "Errors from attribute serialization should be propagated, got {actual:?}"
+    );
+}
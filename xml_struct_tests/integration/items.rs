@@ -0,0 +1,130 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::{Items, XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct GetItem {
+    #[xml_struct(item = "t:ItemId")]
+    item_ids: Vec<ItemId>,
+
+    #[xml_struct(item = "t:FieldURI")]
+    field_uris: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+struct ItemId {
+    id: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct FlattenedItems(#[xml_struct(item = "Value")] Vec<u32>);
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+struct OptionalItems {
+    #[xml_struct(item = "Id")]
+    ids: Vec<Option<String>>,
+}
+
+#[test]
+fn items_helper() {
+    let items = vec![String::from("a"), String::from("b")];
+
+    let actual = serialize_value_as_element(Items::new("Item", &items), "Items")
+        .expect("Failed to serialize items");
+
+    assert_eq!(
+        actual, "<Items><Item>a</Item><Item>b</Item></Items>",
+        "Each entry should be wrapped in an element with the item name"
+    );
+}
+
+#[test]
+fn struct_with_item_fields() {
+    let content = GetItem {
+        item_ids: vec![
+            ItemId {
+                id: String::from("abc"),
+            },
+            ItemId {
+                id: String::from("def"),
+            },
+        ],
+        field_uris: vec![],
+    };
+
+    let expected = r#"<parent><ItemIds><t:ItemId Id="abc"/><t:ItemId Id="def"/></ItemIds><FieldUris></FieldUris></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Each entry of an item field should be wrapped in an element with the item name"
+    );
+
+    let actual: GetItem = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        GetItem {
+            item_ids: vec![
+                ItemId {
+                    id: String::from("abc"),
+                },
+                ItemId {
+                    id: String::from("def"),
+                },
+            ],
+            field_uris: vec![],
+        },
+        "Item fields should be deserialized from elements with the item name"
+    );
+}
+
+#[test]
+fn unnamed_item_field() {
+    let content = FlattenedItems(vec![1, 2, 3]);
+
+    let expected = "<parent><Value>1</Value><Value>2</Value><Value>3</Value></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Unnamed item field should be serialized without an enclosing element"
+    );
+
+    let actual: FlattenedItems =
+        deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        FlattenedItems(vec![1, 2, 3]),
+        "Unnamed item field should be deserialized from elements with the item name"
+    );
+}
+
+#[test]
+fn unexpected_item_name() {
+    let actual: Result<GetItem, _> =
+        deserialize_value_from_str("<parent><ItemIds><t:Other/></ItemIds><FieldUris/></parent>");
+
+    assert!(
+        actual.is_err(),
+        "Entries with a name other than the item name should produce an error"
+    );
+}
+
+#[test]
+fn unmatched_optional_item() {
+    let actual: Result<OptionalItems, _> =
+        deserialize_value_from_str("<parent><Ids><Other>1</Other></Ids></parent>");
+
+    assert!(
+        actual.is_err(),
+        "Entries not consumed as an optional item should produce an error"
+    );
+}
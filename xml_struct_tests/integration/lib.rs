@@ -2,8 +2,23 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+mod attr_list;
+mod cdata;
+mod deserialize;
 mod r#enum;
+mod error_context;
+mod field_hooks;
+mod items;
+mod map;
+mod namespaces;
+mod pointers;
+mod rename;
+mod repeated;
+mod sequences;
+mod skip;
+mod strict;
 mod r#struct;
 mod text_enum;
+mod text_field;
 mod tuple_struct;
 mod unit_struct;
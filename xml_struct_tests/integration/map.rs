@@ -0,0 +1,126 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::BTreeMap;
+
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Settings {
+    values: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Headers {
+    #[xml_struct(map = "entries", item = "t:Header", key = "Name")]
+    headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+enum Message {
+    Properties(#[xml_struct(map = "entries")] BTreeMap<String, String>),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Extensible {
+    #[xml_struct(attribute)]
+    id: String,
+
+    #[xml_struct(map = "attributes")]
+    extra: BTreeMap<String, String>,
+
+    name: String,
+}
+
+fn string_map(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn map_as_elements() {
+    let content = Settings {
+        values: BTreeMap::from([(String::from("Width"), 80), (String::from("Height"), 24)]),
+    };
+
+    let expected = "<parent><Values><Height>24</Height><Width>80</Width></Values></parent>";
+
+    let actual = serialize_value_as_element(&content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Each value should be an element named by its key"
+    );
+
+    let actual: Settings = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(actual, content, "Map should round-trip");
+}
+
+#[test]
+fn map_as_entries() {
+    let content = Headers {
+        headers: string_map(&[("To", "alice@example.com"), ("From", "bob@example.com")]),
+    };
+
+    let expected = r#"<parent><Headers><t:Header Name="From">bob@example.com</t:Header><t:Header Name="To">alice@example.com</t:Header></Headers></parent>"#;
+
+    let actual = serialize_value_as_element(&content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Each entry should be an element with the item name and key attribute"
+    );
+
+    let actual: Headers = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(actual, content, "Map should round-trip");
+}
+
+#[test]
+fn map_as_entries_in_variant() {
+    let content = Message::Properties(string_map(&[("Subject", "Hello")]));
+
+    let expected =
+        r#"<parent><Properties><Entry Key="Subject">Hello</Entry></Properties></parent>"#;
+
+    let actual = serialize_value_as_element(&content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Entries should use the default entry and key names"
+    );
+
+    let actual: Message = deserialize_value_from_str(&actual).expect("Failed to read enum");
+
+    assert_eq!(actual, content, "Map should round-trip");
+}
+
+#[test]
+fn map_as_attributes() {
+    let content = Extensible {
+        id: String::from("abc"),
+        extra: string_map(&[("color", "red"), ("size", "large")]),
+        name: String::from("widget"),
+    };
+
+    let expected = r#"<parent Id="abc" color="red" size="large"><Name>widget</Name></parent>"#;
+
+    let actual = serialize_value_as_element(&content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Each entry should be an attribute on the parent element"
+    );
+
+    let actual: Extensible = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual, content,
+        "Attributes claimed by other fields should not be included in the map"
+    );
+}
@@ -0,0 +1,858 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use xml_struct::{Cdata, Error, QName, XmlDeserialize, XmlSerialize, XmlWriter};
+use xml_struct_tests::{
+    deserialize_value_from_str, serialize_value_as_element, serialize_value_children,
+};
+
+const TYPES_NS: &str = "http://types.example/";
+const MESSAGES_NS: &str = "http://messages.example/";
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("t", TYPES_NS))]
+struct Item {
+    #[xml_struct(ns_prefix = "t")]
+    subject: String,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(default_ns = MESSAGES_NS, ns = ("t", TYPES_NS))]
+struct CreateItem {
+    #[xml_struct(ns_prefix = "t", item = "t:Item")]
+    items: Vec<Item>,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("t", "http://other.example/"))]
+struct Rebound {
+    #[xml_struct(ns_prefix = "t")]
+    item: Item,
+}
+
+#[derive(XmlSerialize)]
+struct Siblings {
+    first: Item,
+    second: Item,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("t", TYPES_NS))]
+struct Export {
+    #[xml_struct(ns_prefix = "t", serialize_with = "write_as_document")]
+    document: Item,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Folder {
+    #[xml_struct(attribute, ns = TYPES_NS)]
+    id: String,
+
+    #[xml_struct(ns = TYPES_NS)]
+    display_name: String,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("t", TYPES_NS))]
+struct GetFolder {
+    folder: Folder,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(ns = TYPES_NS)]
+struct Message {
+    subject: String,
+    body: MessageBody,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(ns = TYPES_NS)]
+enum MessageBody {
+    Text(String),
+    Html(String),
+}
+
+#[test]
+fn nested_declarations_in_scope_are_omitted() {
+    let content = CreateItem {
+        items: vec![
+            Item {
+                subject: String::from("first"),
+            },
+            Item {
+                subject: String::from("second"),
+            },
+        ],
+    };
+
+    let expected = r#"<CreateItem xmlns="http://messages.example/" xmlns:t="http://types.example/"><t:Items><t:Item><t:Subject>first</t:Subject></t:Item><t:Item><t:Subject>second</t:Subject></t:Item></t:Items></CreateItem>"#;
+
+    let actual = serialize_value_as_element(content, "CreateItem").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Declarations already in scope should not be repeated"
+    );
+}
+
+#[test]
+fn nested_declarations_with_new_binding_are_kept() {
+    let content = Rebound {
+        item: Item {
+            subject: String::from("text"),
+        },
+    };
+
+    let expected = r#"<Rebound xmlns:t="http://other.example/"><t:Item xmlns:t="http://types.example/"><t:Subject>text</t:Subject></t:Item></Rebound>"#;
+
+    let actual = serialize_value_as_element(content, "Rebound").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Prefixes bound to a different namespace should be redeclared"
+    );
+}
+
+#[test]
+fn declarations_leave_scope_with_element() {
+    let content = Siblings {
+        first: Item {
+            subject: String::from("first"),
+        },
+        second: Item {
+            subject: String::from("second"),
+        },
+    };
+
+    let expected = r#"<Siblings><First xmlns:t="http://types.example/"><t:Subject>first</t:Subject></First><Second xmlns:t="http://types.example/"><t:Subject>second</t:Subject></Second></Siblings>"#;
+
+    let actual = serialize_value_as_element(content, "Siblings").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Declarations should not remain in scope for sibling elements"
+    );
+
+    let mut writer = XmlWriter::new(Vec::new());
+    Item {
+        subject: String::from("text"),
+    }
+    .serialize_as_element(&mut writer, "Item")
+    .expect("Failed to write struct");
+
+    assert_eq!(
+        writer.namespace_for(Some("t")),
+        None,
+        "No declarations should remain in scope after serialization"
+    );
+}
+
+/// Serializes a value as a separate document, written as a CDATA section.
+fn write_as_document<W>(value: &Item, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
+where
+    W: std::io::Write,
+{
+    let mut document = XmlWriter::new(Vec::new());
+    value.serialize_as_element(&mut document, "Item")?;
+
+    let document = String::from_utf8(document.into_inner()).expect("output should be UTF-8");
+
+    Cdata(document).serialize_as_element(writer, name)
+}
+
+/// A value which writes its own element, declaring a namespace on it.
+struct ManualDeclaration(Item);
+
+impl XmlSerialize for ManualDeclaration {
+    fn serialize_as_element<W>(&self, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        let mut start_tag = BytesStart::new(name);
+        start_tag.push_attribute(("xmlns:t", TYPES_NS));
+
+        writer.write_event(Event::Start(start_tag))?;
+        self.0.serialize_as_element(writer, "Item")?;
+        writer.write_event(Event::End(BytesEnd::new(name)))?;
+
+        Ok(())
+    }
+
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        self.0.serialize_child_nodes(writer)
+    }
+}
+
+#[test]
+fn manual_declarations_are_in_scope() {
+    let content = ManualDeclaration(Item {
+        subject: String::from("text"),
+    });
+
+    let expected = r#"<Manual xmlns:t="http://types.example/"><Item><t:Subject>text</t:Subject></Item></Manual>"#;
+
+    let actual = serialize_value_as_element(content, "Manual").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Declarations on start tags written by manual implementations should be in scope"
+    );
+}
+
+#[test]
+fn nested_documents_have_separate_scopes() {
+    let content = Export {
+        document: Item {
+            subject: String::from("text"),
+        },
+    };
+
+    let expected = r#"<Export xmlns:t="http://types.example/"><t:Document><![CDATA[<Item xmlns:t="http://types.example/"><t:Subject>text</t:Subject></Item>]]></t:Document></Export>"#;
+
+    let actual = serialize_value_as_element(content, "Export").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Declarations in scope in one document should not be in scope in another"
+    );
+}
+
+#[test]
+fn prefix_is_chosen_for_namespace() {
+    let content = Folder {
+        id: String::from("inbox"),
+        display_name: String::from("Inbox"),
+    };
+
+    let expected = r#"<Folder xmlns:ns0="http://types.example/" ns0:Id="inbox"><ns0:DisplayName>Inbox</ns0:DisplayName></Folder>"#;
+
+    let actual = serialize_value_as_element(content, "Folder").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "A prefix should be chosen and declared on the enclosing element"
+    );
+}
+
+#[test]
+fn prefix_in_scope_is_reused() {
+    let content = GetFolder {
+        folder: Folder {
+            id: String::from("inbox"),
+            display_name: String::from("Inbox"),
+        },
+    };
+
+    let expected = r#"<GetFolder xmlns:t="http://types.example/"><Folder t:Id="inbox"><t:DisplayName>Inbox</t:DisplayName></Folder></GetFolder>"#;
+
+    let actual = serialize_value_as_element(content, "GetFolder").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "A prefix already bound to the namespace should be reused"
+    );
+}
+
+#[test]
+fn type_namespace_applies_to_fields_and_variants() {
+    let content = Message {
+        subject: String::from("Hello"),
+        body: MessageBody::Html(String::from("<p>Hi</p>")),
+    };
+
+    let expected = r#"<Message xmlns:ns0="http://types.example/"><ns0:Subject>Hello</ns0:Subject><ns0:Body><ns0:Html>&lt;p&gt;Hi&lt;/p&gt;</ns0:Html></ns0:Body></Message>"#;
+
+    let actual = serialize_value_as_element(content, "Message").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Element fields and variants should be named in the type's namespace"
+    );
+}
+
+#[test]
+fn declaration_is_made_on_variant_element() {
+    let content = MessageBody::Text(String::from("Hi"));
+
+    let expected = r#"<ns0:Text xmlns:ns0="http://types.example/">Hi</ns0:Text>"#;
+
+    let actual = serialize_value_children(content).expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Declaration should be made on the variant's own element"
+    );
+}
+
+#[test]
+fn names_in_namespace_match_any_prefix() {
+    let expected = Folder {
+        id: String::from("inbox"),
+        display_name: String::from("Inbox"),
+    };
+
+    let actual: Folder = deserialize_value_from_str(
+        r#"<Folder xmlns:types="http://types.example/" types:Id="inbox"><types:DisplayName>Inbox</types:DisplayName></Folder>"#,
+    )
+    .expect("Failed to read struct");
+
+    assert_eq!(actual, expected, "Names should be matched by namespace");
+
+    let actual: Folder = deserialize_value_from_str(
+        r#"<Folder xmlns:t="http://types.example/" t:Id="inbox"><DisplayName xmlns="http://types.example/">Inbox</DisplayName></Folder>"#,
+    )
+    .expect("Failed to read struct");
+
+    assert_eq!(
+        actual, expected,
+        "Element names should be matched in the default namespace"
+    );
+
+    let expected = Message {
+        subject: String::from("Hello"),
+        body: MessageBody::Text(String::from("Hi")),
+    };
+
+    let actual: Message = deserialize_value_from_str(
+        r#"<Message xmlns="http://types.example/"><Subject>Hello</Subject><Body><Text>Hi</Text></Body></Message>"#,
+    )
+    .expect("Failed to read struct");
+
+    assert_eq!(actual, expected, "Variants should be matched by namespace");
+}
+
+#[test]
+fn names_in_other_namespace_do_not_match() {
+    let result: Result<Folder, _> = deserialize_value_from_str(
+        r#"<Folder xmlns:t="http://other.example/" t:Id="inbox"><t:DisplayName>Inbox</t:DisplayName></Folder>"#,
+    );
+
+    assert!(
+        result.is_err(),
+        "Names in a different namespace should not match"
+    );
+
+    let result: Result<Folder, _> = deserialize_value_from_str(
+        r#"<Folder xmlns="http://types.example/" Id="inbox"><DisplayName>Inbox</DisplayName></Folder>"#,
+    );
+
+    assert!(
+        result.is_err(),
+        "Unprefixed attributes should not be in the default namespace"
+    );
+}
+
+#[test]
+fn round_trip_with_chosen_prefix() {
+    let content = Message {
+        subject: String::from("Hello"),
+        body: MessageBody::Text(String::from("Hi")),
+    };
+
+    let xml = serialize_value_as_element(&content, "Message").expect("Failed to write struct");
+    let actual: Message = deserialize_value_from_str(&xml).expect("Failed to read struct");
+
+    assert_eq!(actual, content, "Value should survive a round trip");
+}
+
+/// Serializes a value as an element with the given qualified name.
+fn serialize_value_as_qualified_element<T>(value: &T, name: QName<'_>) -> Result<String, Error>
+where
+    T: XmlSerialize,
+{
+    let mut writer: XmlWriter<Vec<u8>> = XmlWriter::new(Vec::new());
+    value.serialize_as_qualified_element(&mut writer, name)?;
+
+    Ok(String::from_utf8(writer.into_inner()).expect("output should be UTF-8"))
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(default_ns = MESSAGES_NS)]
+struct Envelope;
+
+/// A value whose element is named at runtime.
+struct Named<'a> {
+    name: QName<'a>,
+    value: &'a str,
+}
+
+impl XmlSerialize for Named<'_> {
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        self.value.serialize_as_qualified_element(writer, self.name)
+    }
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(default_ns = MESSAGES_NS, ns = ("t", TYPES_NS))]
+struct Body<'a> {
+    #[xml_struct(flatten)]
+    content: Named<'a>,
+}
+
+#[test]
+fn qualified_element_declares_prefix() {
+    let actual =
+        serialize_value_as_qualified_element(&Envelope, QName::with_ns(TYPES_NS, "Envelope"))
+            .expect("Failed to write struct");
+
+    let expected =
+        r#"<ns0:Envelope xmlns="http://messages.example/" xmlns:ns0="http://types.example/"/>"#;
+
+    assert_eq!(
+        actual, expected,
+        "A prefix should be declared on the element itself"
+    );
+
+    let actual = serialize_value_as_qualified_element(&Envelope, QName::local("Envelope"))
+        .expect("Failed to write struct");
+
+    let expected = r#"<Envelope xmlns="http://messages.example/"/>"#;
+
+    assert_eq!(
+        actual, expected,
+        "Names in no namespace should be unprefixed"
+    );
+}
+
+#[test]
+fn qualified_element_reuses_bindings_in_scope() {
+    let content = Body {
+        content: Named {
+            name: QName::with_ns(TYPES_NS, "Subject"),
+            value: "Hello",
+        },
+    };
+
+    let expected = r#"<Body xmlns="http://messages.example/" xmlns:t="http://types.example/"><t:Subject>Hello</t:Subject></Body>"#;
+
+    let actual = serialize_value_as_element(content, "Body").expect("Failed to write struct");
+
+    assert_eq!(actual, expected, "A prefix in scope should be reused");
+
+    let content = Body {
+        content: Named {
+            name: QName::with_ns(MESSAGES_NS, "Subject"),
+            value: "Hello",
+        },
+    };
+
+    let expected = r#"<Body xmlns="http://messages.example/" xmlns:t="http://types.example/"><Subject>Hello</Subject></Body>"#;
+
+    let actual = serialize_value_as_element(content, "Body").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "A default namespace in scope should be reused"
+    );
+}
+
+#[test]
+fn qualified_element_undeclares_default_namespace() {
+    let content = Body {
+        content: Named {
+            name: QName::local("Subject"),
+            value: "Hello",
+        },
+    };
+
+    let expected = r#"<Body xmlns="http://messages.example/" xmlns:t="http://types.example/"><Subject xmlns="">Hello</Subject></Body>"#;
+
+    let actual = serialize_value_as_element(content, "Body").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "The default namespace should be undeclared for names in no namespace"
+    );
+}
+
+#[test]
+fn qualified_element_rejects_invalid_local_name() {
+    let result =
+        serialize_value_as_qualified_element(&Envelope, QName::with_ns(TYPES_NS, "t:Envelope"));
+
+    assert!(
+        matches!(result, Err(Error::IllFormed(_))),
+        "Local names containing a colon should be rejected, got {result:?}"
+    );
+
+    for local in ["", "1Envelope", "Big Envelope"] {
+        let result =
+            serialize_value_as_qualified_element(&Envelope, QName::with_ns(TYPES_NS, local));
+
+        assert!(
+            matches!(result, Err(Error::IllFormed(_))),
+            "Local name `{local}` should be rejected, got {result:?}"
+        );
+    }
+}
+
+#[test]
+fn qualified_element_treats_empty_namespace_as_none() {
+    let actual = serialize_value_as_qualified_element(&Envelope, QName::with_ns("", "Envelope"))
+        .expect("Failed to write struct");
+
+    let expected = r#"<Envelope xmlns="http://messages.example/"/>"#;
+
+    assert_eq!(
+        actual, expected,
+        "Names in the empty namespace should be unprefixed"
+    );
+
+    let content = Body {
+        content: Named {
+            name: QName::with_ns("", "Subject"),
+            value: "Hello",
+        },
+    };
+
+    let expected = r#"<Body xmlns="http://messages.example/" xmlns:t="http://types.example/"><Subject xmlns="">Hello</Subject></Body>"#;
+
+    let actual = serialize_value_as_element(content, "Body").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "The default namespace should be undeclared for names in the empty namespace"
+    );
+}
+
+#[test]
+fn qualified_element_rejects_xmlns_namespace() {
+    let result = serialize_value_as_qualified_element(
+        &Envelope,
+        QName::with_ns("http://www.w3.org/2000/xmlns/", "Envelope"),
+    );
+
+    assert!(
+        matches!(result, Err(Error::IllFormed(_))),
+        "Names in the xmlns namespace should be rejected, got {result:?}"
+    );
+}
+
+/// A value which writes its own element without declaring any namespaces.
+struct ManualElement(&'static str);
+
+impl XmlSerialize for ManualElement {
+    fn serialize_as_element<W>(&self, writer: &mut XmlWriter<W>, name: &str) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        writer.write_event(Event::Start(BytesStart::new(name)))?;
+        self.serialize_child_nodes(writer)?;
+        writer.write_event(Event::End(BytesEnd::new(name)))?;
+
+        Ok(())
+    }
+
+    fn serialize_child_nodes<W>(&self, writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        self.0.serialize_child_nodes(writer)
+    }
+}
+
+/// A value which writes nothing at all, even when asked for an element.
+struct Nothing;
+
+impl XmlSerialize for Nothing {
+    fn serialize_as_element<W>(&self, _writer: &mut XmlWriter<W>, _name: &str) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        Ok(())
+    }
+
+    fn serialize_child_nodes<W>(&self, _writer: &mut XmlWriter<W>) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        Ok(())
+    }
+}
+
+#[derive(XmlSerialize)]
+struct HookInNamespace {
+    #[xml_struct(ns = TYPES_NS, serialize_with = "write_manual_element")]
+    subject: &'static str,
+}
+
+/// Writes text as an element without declaring any namespaces.
+fn write_manual_element<W>(
+    value: &&'static str,
+    writer: &mut XmlWriter<W>,
+    name: &str,
+) -> Result<(), Error>
+where
+    W: std::io::Write,
+{
+    ManualElement(value).serialize_as_element(writer, name)
+}
+
+#[test]
+fn qualified_element_with_manual_override_declares_prefix() {
+    let actual = serialize_value_as_qualified_element(
+        &ManualElement("text"),
+        QName::with_ns(TYPES_NS, "Manual"),
+    )
+    .expect("Failed to write value");
+
+    let expected = r#"<ns0:Manual xmlns:ns0="http://types.example/">text</ns0:Manual>"#;
+
+    assert_eq!(
+        actual, expected,
+        "A prefix should be declared on an element written by a manual implementation"
+    );
+}
+
+#[test]
+fn hook_in_namespace_uses_declared_prefix() {
+    let content = HookInNamespace { subject: "text" };
+
+    let expected =
+        r#"<Hook xmlns:ns0="http://types.example/"><ns0:Subject>text</ns0:Subject></Hook>"#;
+
+    let actual = serialize_value_as_element(content, "Hook").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Elements written by hooks should be named with a declared prefix"
+    );
+}
+
+#[test]
+fn qualified_names_do_not_outlive_their_element() {
+    let mut writer = XmlWriter::new(Vec::new());
+
+    Nothing
+        .serialize_as_qualified_element(&mut writer, QName::with_ns(TYPES_NS, "Skipped"))
+        .expect("Failed to write value");
+
+    // An element of the name chosen above, written later, must not receive the
+    // declaration made for it.
+    writer
+        .write_event(Event::Empty(BytesStart::new("ns0:Skipped")))
+        .expect("Failed to write element");
+
+    ManualElement("text")
+        .serialize_as_qualified_element(&mut writer, QName::with_ns(MESSAGES_NS, "Later"))
+        .expect("Failed to write value");
+
+    let actual = String::from_utf8(writer.into_inner()).expect("Output should be UTF-8");
+
+    let expected =
+        r#"<ns0:Skipped/><ns0:Later xmlns:ns0="http://messages.example/">text</ns0:Later>"#;
+
+    assert_eq!(
+        actual, expected,
+        "Declarations for a qualified name should only be made on its own element"
+    );
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("t", TYPES_NS))]
+struct ItemFields {
+    #[xml_struct(ns_prefix = "t")]
+    subject: String,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(default_ns = MESSAGES_NS)]
+struct FlattenedFields {
+    #[xml_struct(flatten)]
+    first: ItemFields,
+
+    #[xml_struct(flatten)]
+    second: Option<ItemFields>,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(default_ns = "urn:inner")]
+struct InnerDefaultFields {
+    b: String,
+}
+
+#[derive(XmlSerialize)]
+struct OuterWithoutDefault {
+    own: String,
+
+    #[xml_struct(flatten)]
+    inner: InnerDefaultFields,
+}
+
+#[derive(XmlSerialize)]
+struct UnnamedFields(FlattenedFields);
+
+#[derive(XmlSerialize)]
+struct SequenceOfFields {
+    items: Vec<ItemFields>,
+}
+
+#[derive(XmlSerialize)]
+enum VariantWithFlattenedFields {
+    Item {
+        #[xml_struct(flatten)]
+        fields: ItemFields,
+    },
+}
+
+#[test]
+fn flattened_field_declarations_are_hoisted() {
+    let content = FlattenedFields {
+        first: ItemFields {
+            subject: String::from("first"),
+        },
+        second: Some(ItemFields {
+            subject: String::from("second"),
+        }),
+    };
+
+    let expected = r#"<Fields xmlns="http://messages.example/" xmlns:t="http://types.example/"><t:Subject>first</t:Subject><t:Subject>second</t:Subject></Fields>"#;
+
+    let actual = serialize_value_as_element(content, "Fields").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Declarations of flattened fields should be made once on the enclosing element"
+    );
+
+    let content = OuterWithoutDefault {
+        own: String::from("o"),
+        inner: InnerDefaultFields {
+            b: String::from("b"),
+        },
+    };
+
+    let expected = "<FlatOuter><Own>o</Own><B>b</B></FlatOuter>";
+
+    let actual = serialize_value_as_element(content, "FlatOuter").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Default namespaces of flattened fields should not change that of the enclosing element"
+    );
+}
+
+#[test]
+fn unnamed_field_declarations_are_hoisted() {
+    let content = UnnamedFields(FlattenedFields {
+        first: ItemFields {
+            subject: String::from("first"),
+        },
+        second: None,
+    });
+
+    let expected =
+        r#"<Fields xmlns:t="http://types.example/"><t:Subject>first</t:Subject></Fields>"#;
+
+    let actual = serialize_value_as_element(content, "Fields").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Prefixed declarations of nested content nodes should be hoisted transitively"
+    );
+}
+
+#[test]
+fn sequence_declarations_are_hoisted() {
+    let content = SequenceOfFields {
+        items: vec![
+            ItemFields {
+                subject: String::from("first"),
+            },
+            ItemFields {
+                subject: String::from("second"),
+            },
+        ],
+    };
+
+    let expected = r#"<Sequence><Items xmlns:t="http://types.example/"><t:Subject>first</t:Subject><t:Subject>second</t:Subject></Items></Sequence>"#;
+
+    let actual = serialize_value_as_element(content, "Sequence").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Declarations of sequence entries should be made on the sequence's element"
+    );
+}
+
+#[test]
+fn variant_flattened_field_declarations_are_hoisted() {
+    let content = VariantWithFlattenedFields::Item {
+        fields: ItemFields {
+            subject: String::from("text"),
+        },
+    };
+
+    let expected = r#"<Item xmlns:t="http://types.example/"><t:Subject>text</t:Subject></Item>"#;
+
+    let actual = serialize_value_children(content).expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Declarations of flattened fields should be made on the variant's element"
+    );
+}
+
+#[test]
+fn declared_namespaces_of_derived_types() {
+    assert_eq!(
+        ItemFields::declared_namespaces(),
+        vec![(Some("t"), TYPES_NS)],
+        "Struct should declare its own namespaces"
+    );
+
+    assert_eq!(
+        FlattenedFields::declared_namespaces(),
+        vec![
+            (None, MESSAGES_NS),
+            (Some("t"), TYPES_NS),
+            (Some("t"), TYPES_NS)
+        ],
+        "Struct should include the namespaces of its flattened fields"
+    );
+
+    assert_eq!(
+        VariantWithFlattenedFields::declared_namespaces(),
+        vec![],
+        "Enum variants should declare namespaces on their own elements"
+    );
+}
+
+#[test]
+fn converted_quick_xml_writer() {
+    let mut writer = XmlWriter::from(quick_xml::Writer::new(Vec::new()));
+
+    writer
+        .write_event(Event::Start(
+            BytesStart::new("Root").with_attributes([("xmlns:t", TYPES_NS)]),
+        ))
+        .expect("Failed to write start tag");
+
+    Item {
+        subject: String::from("text"),
+    }
+    .serialize_as_element(&mut writer, "Item")
+    .expect("Failed to write struct");
+
+    writer
+        .write_event(Event::End(BytesEnd::new("Root")))
+        .expect("Failed to write end tag");
+
+    writer.get_mut().extend_from_slice(b"<!-- done -->");
+
+    let actual = String::from_utf8(writer.into_inner()).expect("Output should be UTF-8");
+    let expected = r#"<Root xmlns:t="http://types.example/"><Item><t:Subject>text</t:Subject></Item></Root><!-- done -->"#;
+
+    assert_eq!(
+        actual, expected,
+        "Writer converted from quick_xml should track declarations and expose its output"
+    );
+}
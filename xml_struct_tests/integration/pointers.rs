@@ -0,0 +1,98 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Folder {
+    #[xml_struct(attribute)]
+    display_name: Cow<'static, str>,
+
+    #[xml_struct(repeated)]
+    child_folder: Vec<Folder>,
+
+    parent: Option<Box<Folder>>,
+}
+
+#[derive(Debug, PartialEq, XmlSerialize)]
+struct Request<'a> {
+    #[xml_struct(attribute)]
+    shape: Rc<BaseShape>,
+
+    folder: &'a Folder,
+
+    #[xml_struct(flatten)]
+    additional: Arc<Additional>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(text)]
+enum BaseShape {
+    IdOnly,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Additional {
+    #[xml_struct(attribute)]
+    set_on_parent: String,
+
+    field_uri: String,
+}
+
+fn folder(name: &'static str, child_folder: Vec<Folder>) -> Folder {
+    Folder {
+        display_name: Cow::Borrowed(name),
+        child_folder,
+        parent: None,
+    }
+}
+
+#[test]
+fn recursive_boxed_struct() {
+    let mut work = folder("Work", vec![]);
+    work.parent = Some(Box::new(folder("Root", vec![])));
+
+    let content = folder("Inbox", vec![work, folder("Home", vec![])]);
+
+    let expected = r#"<parent DisplayName="Inbox"><ChildFolder DisplayName="Work"><Parent DisplayName="Root"></Parent></ChildFolder><ChildFolder DisplayName="Home"></ChildFolder></parent>"#;
+
+    let actual = serialize_value_as_element(&content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Boxed values should be serialized identically to the values they contain"
+    );
+
+    let actual: Folder = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual, content,
+        "Boxed values should be deserialized identically to the values they contain"
+    );
+}
+
+#[test]
+fn shared_and_borrowed_values() {
+    let inbox = folder("Inbox", vec![]);
+    let content = Request {
+        shape: Rc::new(BaseShape::IdOnly),
+        folder: &inbox,
+        additional: Arc::new(Additional {
+            set_on_parent: String::from("value"),
+            field_uri: String::from("item:Subject"),
+        }),
+    };
+
+    let expected = r#"<parent Shape="IdOnly" SetOnParent="value"><Folder DisplayName="Inbox"></Folder><FieldUri>item:Subject</FieldUri></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Shared and borrowed values should be serialized identically to the values they point to"
+    );
+}
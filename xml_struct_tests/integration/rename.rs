@@ -0,0 +1,255 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(rename_all = "camelCase")]
+struct CamelCaseStruct {
+    #[xml_struct(attribute)]
+    some_attr: String,
+
+    #[xml_struct(attribute, rename = "ATTR", ns_prefix = "foo")]
+    renamed_attr: String,
+
+    child_field: String,
+
+    #[xml_struct(rename = "explicit-name")]
+    renamed_child: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(rename_all = "kebab-case")]
+enum KebabCaseEnum {
+    UnitVariant,
+    StructVariant { field_name: String },
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(text, rename_all = "snake_case")]
+enum SnakeCaseTextEnum {
+    IdOnly,
+    AllProperties,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(text)]
+enum ServerVersion {
+    #[xml_struct(rename = "1.0")]
+    V1,
+
+    #[xml_struct(rename = "Exchange2013_SP1")]
+    Exchange2013Sp1,
+
+    Exchange2016,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(rename_all = "kebab-case", variant_ns_prefix = "t")]
+enum EnumWithRenamedVariants {
+    #[xml_struct(rename = "IdOnly")]
+    IdOnly,
+
+    AllProperties {
+        #[xml_struct(attribute)]
+        version: ServerVersion,
+    },
+}
+
+#[derive(XmlSerialize)]
+struct AllRules {
+    #[xml_struct(attribute)]
+    pascal: PascalCaseTextEnum,
+
+    #[xml_struct(attribute)]
+    lower: LowercaseTextEnum,
+
+    #[xml_struct(attribute)]
+    upper: UppercaseTextEnum,
+
+    snake: SnakeCaseStruct,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(text, rename_all = "PascalCase")]
+enum PascalCaseTextEnum {
+    IdOnly,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(text, rename_all = "lowercase")]
+enum LowercaseTextEnum {
+    IdOnly,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(text, rename_all = "UPPERCASE")]
+enum UppercaseTextEnum {
+    IdOnly,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(rename_all = "snake_case")]
+struct SnakeCaseStruct {
+    field_name: String,
+
+    r#type: String,
+}
+
+#[test]
+fn struct_with_rename_all() {
+    let content = CamelCaseStruct {
+        some_attr: String::from("attr value"),
+        renamed_attr: String::from("renamed attr value"),
+        child_field: String::from("child value"),
+        renamed_child: String::from("renamed child value"),
+    };
+
+    let expected = r#"<parent someAttr="attr value" foo:ATTR="renamed attr value"><childField>child value</childField><explicit-name>renamed child value</explicit-name></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Field names should follow `rename_all` unless explicitly renamed"
+    );
+
+    let actual: CamelCaseStruct =
+        deserialize_value_from_str(expected).expect("Failed to read struct");
+
+    assert_eq!(
+        actual.renamed_child, "renamed child value",
+        "Renamed fields should be deserialized by their new names"
+    );
+}
+
+#[test]
+fn enum_with_rename_all() {
+    let content = KebabCaseEnum::StructVariant {
+        field_name: String::from("some content"),
+    };
+
+    let expected =
+        "<parent><struct-variant><field-name>some content</field-name></struct-variant></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Variant and field names should follow `rename_all`"
+    );
+
+    let actual: KebabCaseEnum = deserialize_value_from_str("<parent><unit-variant/></parent>")
+        .expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        KebabCaseEnum::UnitVariant,
+        "Variants should be deserialized by their converted names"
+    );
+}
+
+#[test]
+fn text_enum_with_rename_all() {
+    let content = SnakeCaseTextEnum::AllProperties;
+
+    let expected = "<parent>all_properties</parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(actual, expected, "Text values should follow `rename_all`");
+
+    let actual: SnakeCaseTextEnum =
+        deserialize_value_from_str("<parent>id_only</parent>").expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        SnakeCaseTextEnum::IdOnly,
+        "Text values should be deserialized by their converted names"
+    );
+}
+
+#[test]
+fn text_enum_with_renamed_variants() {
+    let content = ServerVersion::V1;
+
+    let expected = "<parent>1.0</parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Renamed text variants should use the specified value"
+    );
+
+    let actual: ServerVersion = deserialize_value_from_str("<parent>Exchange2013_SP1</parent>")
+        .expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        ServerVersion::Exchange2013Sp1,
+        "Renamed text variants should be deserialized from the specified value"
+    );
+}
+
+#[test]
+fn enum_with_renamed_variants() {
+    let content = EnumWithRenamedVariants::IdOnly;
+
+    let expected = "<parent><t:IdOnly/></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Renamed variants should override `rename_all`"
+    );
+
+    let content = EnumWithRenamedVariants::AllProperties {
+        version: ServerVersion::Exchange2013Sp1,
+    };
+
+    let expected = r#"<parent><t:all-properties version="Exchange2013_SP1"/></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Renamed text variants should be used as attribute values"
+    );
+
+    let actual: EnumWithRenamedVariants =
+        deserialize_value_from_str(expected).expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        EnumWithRenamedVariants::AllProperties {
+            version: ServerVersion::Exchange2013Sp1
+        },
+        "Renamed text variants should be deserialized from attribute values"
+    );
+}
+
+#[test]
+fn all_rename_rules() {
+    let content = AllRules {
+        pascal: PascalCaseTextEnum::IdOnly,
+        lower: LowercaseTextEnum::IdOnly,
+        upper: UppercaseTextEnum::IdOnly,
+        snake: SnakeCaseStruct {
+            field_name: String::from("a"),
+            r#type: String::from("b"),
+        },
+    };
+
+    let expected = r#"<parent Pascal="IdOnly" Lower="idonly" Upper="IDONLY"><Snake><field_name>a</field_name><type>b</type></Snake></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Each case convention should be applied to the appropriate names"
+    );
+}
@@ -0,0 +1,102 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Message {
+    subject: String,
+
+    #[xml_struct(repeated)]
+    to: Vec<Mailbox>,
+
+    #[xml_struct(repeated, skip_serializing_if = "Vec::is_empty")]
+    cc: Vec<Mailbox>,
+
+    body: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+struct Mailbox {
+    address: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+enum Recipients {
+    List {
+        name: String,
+
+        #[xml_struct(repeated)]
+        entry: Vec<String>,
+    },
+}
+
+fn mailbox(address: &str) -> Mailbox {
+    Mailbox {
+        address: String::from(address),
+    }
+}
+
+#[test]
+fn struct_with_repeated_fields() {
+    let content = Message {
+        subject: String::from("Hello"),
+        to: vec![mailbox("a@example.com"), mailbox("b@example.com")],
+        cc: vec![],
+        body: String::from("Hi!"),
+    };
+
+    let expected = r#"<parent><Subject>Hello</Subject><To Address="a@example.com"/><To Address="b@example.com"/><Body>Hi!</Body></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Each entry of a repeated field should be serialized as a sibling element"
+    );
+
+    let actual: Message = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        Message {
+            subject: String::from("Hello"),
+            to: vec![mailbox("a@example.com"), mailbox("b@example.com")],
+            cc: vec![],
+            body: String::from("Hi!"),
+        },
+        "Repeated fields should be deserialized from consecutive sibling elements"
+    );
+}
+
+#[test]
+fn enum_with_repeated_field() {
+    let content = Recipients::List {
+        name: String::from("team"),
+        entry: vec![String::from("a"), String::from("b")],
+    };
+
+    let expected = r#"<parent><List Name="team"><Entry>a</Entry><Entry>b</Entry></List></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Repeated fields should be serialized as elements despite attribute default"
+    );
+
+    let actual: Recipients = deserialize_value_from_str(&actual).expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        Recipients::List {
+            name: String::from("team"),
+            entry: vec![String::from("a"), String::from("b")],
+        },
+        "Repeated variant fields should be deserialized from sibling elements"
+    );
+}
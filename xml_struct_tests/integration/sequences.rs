@@ -0,0 +1,81 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, XmlDeserialize, XmlSerialize)]
+enum Entry {
+    A,
+    B,
+    C(u8),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Collections {
+    array: [Entry; 2],
+    deque: VecDeque<Entry>,
+    ordered: BTreeSet<Entry>,
+    unordered: HashSet<Entry>,
+}
+
+#[test]
+fn collections_round_trip() {
+    let content = Collections {
+        array: [Entry::B, Entry::A],
+        deque: VecDeque::from([Entry::C(1), Entry::A]),
+        ordered: BTreeSet::from([Entry::C(2), Entry::B, Entry::A]),
+        unordered: HashSet::from([Entry::C(3)]),
+    };
+
+    let expected = "<parent><Array><B/><A/></Array><Deque><C>1</C><A/></Deque><Ordered><A/><B/><C>2</C></Ordered><Unordered><C>3</C></Unordered></parent>";
+
+    let actual = serialize_value_as_element(&content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Collections should be serialized as consecutive content nodes"
+    );
+
+    let actual: Collections = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual, content,
+        "Collections should be deserialized from consecutive content nodes"
+    );
+}
+
+#[test]
+fn array_with_wrong_length() {
+    let actual: Result<[Entry; 2], _> = deserialize_value_from_str("<parent><A/><B/><A/></parent>");
+
+    assert!(
+        actual.is_err(),
+        "Deserializing an array with the wrong number of elements should fail"
+    );
+}
+
+#[test]
+fn tuple_content_model() {
+    let content = (Entry::A, Entry::C(5), Entry::B);
+
+    let expected = "<Envelope><A/><C>5</C><B/></Envelope>";
+
+    let actual = serialize_value_as_element(&content, "Envelope").expect("Failed to write tuple");
+
+    assert_eq!(
+        actual, expected,
+        "Tuple members should be serialized as consecutive content nodes"
+    );
+
+    let actual: (Entry, Entry, Entry) =
+        deserialize_value_from_str(&actual).expect("Failed to read tuple");
+
+    assert_eq!(
+        actual, content,
+        "Tuple members should be deserialized from consecutive content nodes"
+    );
+}
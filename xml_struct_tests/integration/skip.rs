@@ -0,0 +1,223 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct StructWithSkippedFields {
+    #[xml_struct(attribute, skip)]
+    skipped_attr: String,
+
+    #[xml_struct(attribute, skip_serializing_if = "String::is_empty")]
+    conditional_attr: String,
+
+    #[xml_struct(skip)]
+    skipped_child: Vec<String>,
+
+    #[xml_struct(skip_serializing_if = "is_zero")]
+    conditional_child: u32,
+
+    #[xml_struct(flatten, skip)]
+    skipped_flattened: String,
+
+    #[xml_struct(flatten, skip_serializing_if = "Option::is_none")]
+    conditional_flattened: Option<String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+enum EnumWithSkippedFields {
+    StructVariant {
+        #[xml_struct(attribute, skip)]
+        skipped_attr: String,
+
+        #[xml_struct(skip_serializing_if = "is_zero")]
+        conditional_child: u32,
+    },
+    TupleVariant(#[xml_struct(skip)] String, u32),
+}
+
+#[derive(XmlSerialize)]
+enum EnumWithGenericConditions {
+    StructVariant {
+        #[xml_struct(attribute, skip_serializing_if = "is_default")]
+        attr: String,
+
+        #[xml_struct(skip_serializing_if = "is_default")]
+        child: u32,
+    },
+    TupleVariant(#[xml_struct(skip_serializing_if = "is_default")] u32),
+}
+
+#[derive(XmlSerialize)]
+struct StructWithOnlySkippedChildren {
+    #[xml_struct(attribute)]
+    attr: &'static str,
+
+    #[xml_struct(skip)]
+    _cache: Vec<u8>,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_default<T>(value: &T) -> bool
+where
+    T: Default + PartialEq,
+{
+    *value == T::default()
+}
+
+#[test]
+fn struct_with_skipped_fields() {
+    let content = StructWithSkippedFields {
+        skipped_attr: String::from("skipped attr"),
+        conditional_attr: String::from("conditional attr"),
+        skipped_child: vec![String::from("skipped child")],
+        conditional_child: 17,
+        skipped_flattened: String::from("skipped flattened"),
+        conditional_flattened: Some(String::from("conditional flattened")),
+    };
+
+    let expected = r#"<parent ConditionalAttr="conditional attr"><ConditionalChild>17</ConditionalChild>conditional flattened</parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Skipped fields should be omitted, while conditional fields should be serialized"
+    );
+
+    let actual: StructWithSkippedFields =
+        deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        StructWithSkippedFields {
+            skipped_attr: String::new(),
+            conditional_attr: String::from("conditional attr"),
+            skipped_child: vec![],
+            conditional_child: 17,
+            skipped_flattened: String::new(),
+            conditional_flattened: Some(String::from("conditional flattened")),
+        },
+        "Skipped fields should be deserialized as their default values"
+    );
+}
+
+#[test]
+fn struct_with_conditions_met() {
+    let content = StructWithSkippedFields {
+        skipped_attr: String::from("skipped attr"),
+        conditional_attr: String::new(),
+        skipped_child: vec![],
+        conditional_child: 0,
+        skipped_flattened: String::new(),
+        conditional_flattened: None,
+    };
+
+    let expected = "<parent></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Fields should be omitted when their skip conditions are met"
+    );
+}
+
+#[test]
+fn struct_with_only_skipped_children() {
+    let content = StructWithOnlySkippedChildren {
+        attr: "value",
+        _cache: vec![1, 2, 3],
+    };
+
+    let expected = r#"<parent Attr="value"/>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Struct with only skipped child fields should be an empty element"
+    );
+}
+
+#[test]
+fn enum_with_skipped_fields() {
+    let content = EnumWithSkippedFields::StructVariant {
+        skipped_attr: String::from("skipped attr"),
+        conditional_child: 0,
+    };
+
+    let expected = "<parent><StructVariant></StructVariant></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(actual, expected, "Skipped variant fields should be omitted");
+
+    let content = EnumWithSkippedFields::TupleVariant(String::from("skipped"), 5);
+
+    let expected = "<parent><TupleVariant>5</TupleVariant></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Skipped tuple variant fields should be omitted"
+    );
+
+    let actual: EnumWithSkippedFields =
+        deserialize_value_from_str(&actual).expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        EnumWithSkippedFields::TupleVariant(String::new(), 5),
+        "Skipped tuple variant fields should be deserialized as their default values"
+    );
+}
+
+#[test]
+fn enum_with_generic_conditions() {
+    let content = EnumWithGenericConditions::StructVariant {
+        attr: String::new(),
+        child: 0,
+    };
+
+    let expected = "<parent><StructVariant></StructVariant></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Generic conditions should be checked against variant field values"
+    );
+
+    let content = EnumWithGenericConditions::StructVariant {
+        attr: String::from("value"),
+        child: 3,
+    };
+
+    let expected =
+        r#"<parent><StructVariant Attr="value"><Child>3</Child></StructVariant></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Variant fields should be serialized when generic conditions aren't met"
+    );
+
+    let content = EnumWithGenericConditions::TupleVariant(0);
+
+    let expected = "<parent><TupleVariant></TupleVariant></parent>";
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Generic conditions should be checked against tuple variant field values"
+    );
+}
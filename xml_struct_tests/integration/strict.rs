@@ -0,0 +1,194 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use quick_xml::events::{BytesStart, BytesText, Event};
+use xml_struct::{Error, XmlSerialize, XmlWriter};
+use xml_struct_tests::serialize_value_as_element;
+
+#[derive(XmlSerialize)]
+struct Message {
+    #[xml_struct(attribute)]
+    id: String,
+
+    subject: String,
+
+    #[xml_struct(cdata)]
+    body: String,
+}
+
+fn message(id: &str, subject: &str, body: &str) -> Message {
+    Message {
+        id: id.to_owned(),
+        subject: subject.to_owned(),
+        body: body.to_owned(),
+    }
+}
+
+/// Serializes a value as an element with a strict writer, returning the
+/// error and everything written to the writer.
+fn serialize_strict_error<T>(value: T, name: &str) -> (Error, String)
+where
+    T: XmlSerialize,
+{
+    let mut writer = XmlWriter::new(Vec::new()).strict();
+    let err = value
+        .serialize_as_element(&mut writer, name)
+        .expect_err("Serialization should fail");
+
+    let written = String::from_utf8(writer.into_inner()).expect("Output should be UTF-8");
+
+    (err, written)
+}
+
+#[test]
+fn strict_well_formed_output() {
+    let content = message("abc", "Caf\u{E9} & \u{1F600}", "a\tb\r\nc");
+
+    let mut writer = XmlWriter::new(Vec::new()).strict();
+    assert!(writer.is_strict(), "Writer should be in strict mode");
+
+    content
+        .serialize_as_element(&mut writer, "t:Message")
+        .expect("Failed to write struct");
+
+    let actual = String::from_utf8(writer.into_inner()).expect("Output should be UTF-8");
+
+    assert_eq!(
+        actual,
+        "<t:Message Id=\"abc\"><Subject>Caf\u{E9} &amp; \u{1F600}</Subject><Body><![CDATA[a\tb\r\nc]]></Body></t:Message>",
+        "Well-formed output should be written unchanged"
+    );
+
+    let mut writer = XmlWriter::new(Vec::new()).strict();
+    String::from("text")
+        .serialize_child_nodes(&mut writer)
+        .expect("Failed to write text");
+
+    assert_eq!(writer.into_inner(), b"text");
+}
+
+#[test]
+fn strict_with_indentation() {
+    let content = message("abc", "Subject", "Body");
+
+    let mut writer = XmlWriter::new_with_indent(Vec::new(), b' ', 2).strict();
+    content
+        .serialize_as_element(&mut writer, "Message")
+        .expect("Failed to write struct");
+
+    let actual = String::from_utf8(writer.into_inner()).expect("Output should be UTF-8");
+
+    assert_eq!(
+        actual,
+        "<Message Id=\"abc\">\n  <Subject>Subject</Subject>\n  <Body><![CDATA[Body]]></Body>\n</Message>",
+        "The writer's indentation should be applied in strict mode"
+    );
+}
+
+#[test]
+fn strict_rejects_illegal_characters() {
+    let (err, written) = serialize_strict_error(message("abc", "bell\u{7}", ""), "Message");
+
+    assert!(
+        matches!(err.root_cause(), Error::IllFormed(_)),
+        "Illegal characters in text should be rejected, got {err:?}"
+    );
+    assert_eq!(
+        written, r#"<Message Id="abc"><Subject>"#,
+        "Nothing should be written from the rejected event onward"
+    );
+
+    let (err, written) = serialize_strict_error(message("\u{0}", "", ""), "Message");
+
+    assert!(
+        matches!(err.root_cause(), Error::IllFormed(_)),
+        "Illegal characters in attribute values should be rejected, got {err:?}"
+    );
+    assert!(
+        written.is_empty(),
+        "Rejected start tag should not be written"
+    );
+
+    let (err, _) = serialize_strict_error(message("", "", "\u{FFFE}"), "Message");
+
+    assert!(
+        matches!(err.root_cause(), Error::IllFormed(_)),
+        "Illegal characters in CDATA sections should be rejected, got {err:?}"
+    );
+}
+
+#[test]
+fn strict_rejects_invalid_names() {
+    for name in ["", "1Message", "Mess age", "-Message", "Message>"] {
+        let (err, written) = serialize_strict_error(String::from("text"), name);
+
+        assert!(
+            matches!(err.root_cause(), Error::IllFormed(_)),
+            "Name {name:?} should be rejected, got {err:?}"
+        );
+        assert!(
+            written.is_empty(),
+            "Rejected start tag should not be written"
+        );
+    }
+
+    /// A value written with an attribute name which the derive would reject.
+    struct BadAttribute;
+
+    impl XmlSerialize for BadAttribute {
+        fn serialize_as_element<W>(
+            &self,
+            writer: &mut XmlWriter<W>,
+            name: &str,
+        ) -> Result<(), Error>
+        where
+            W: std::io::Write,
+        {
+            let mut start_tag = BytesStart::new(name);
+            start_tag.push_attribute(("bad name", "value"));
+            writer.write_event(Event::Empty(start_tag))?;
+
+            Ok(())
+        }
+
+        fn serialize_child_nodes<W>(&self, _writer: &mut XmlWriter<W>) -> Result<(), Error>
+        where
+            W: std::io::Write,
+        {
+            Ok(())
+        }
+    }
+
+    let (err, _) = serialize_strict_error(BadAttribute, "Element");
+
+    assert!(
+        matches!(err.root_cause(), Error::IllFormed(_)),
+        "Invalid attribute names should be rejected, got {err:?}"
+    );
+}
+
+#[test]
+fn strict_checks_events_written_directly() {
+    let mut writer = XmlWriter::new(Vec::new()).strict();
+
+    let err = writer
+        .write_event(Event::Text(BytesText::new("bell\u{7}")))
+        .expect_err("Illegal characters should be rejected");
+
+    assert!(
+        matches!(err, Error::IllFormed(_)),
+        "Events written outside of derived implementations should be checked, got {err:?}"
+    );
+}
+
+#[test]
+fn non_strict_writes_illegal_characters() {
+    let actual = serialize_value_as_element(String::from("bell\u{7}"), "Message")
+        .expect("Failed to write string");
+
+    assert_eq!(
+        actual, "<Message>bell\u{7}</Message>",
+        "Output should not be checked outside of strict mode"
+    );
+}
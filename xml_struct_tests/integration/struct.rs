@@ -44,6 +44,21 @@ struct StructWithFlattenedField {
     more_complex_field_name: String,
 }
 
+#[derive(XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+struct StructWithAttributesByDefault {
+    str_attr: &'static str,
+
+    #[xml_struct(ns_prefix = "other_ns")]
+    string_attr: String,
+
+    #[xml_struct(element)]
+    child: ChildType,
+
+    #[xml_struct(flatten)]
+    flattened_child: ChildType,
+}
+
 struct ChildType {
     _grandchild: &'static str,
 }
@@ -52,7 +67,7 @@ impl ChildType {
     #[allow(dead_code)]
     fn serialize_child_nodes<W>(
         &self,
-        _writer: &mut quick_xml::Writer<W>,
+        _writer: &mut xml_struct::XmlWriter<W>,
     ) -> Result<(), xml_struct::Error>
     where
         W: std::io::Write,
@@ -67,7 +82,7 @@ impl ChildType {
 impl XmlSerialize for ChildType {
     fn serialize_child_nodes<W>(
         &self,
-        writer: &mut quick_xml::Writer<W>,
+        writer: &mut xml_struct::XmlWriter<W>,
     ) -> Result<(), xml_struct::Error>
     where
         W: std::io::Write,
@@ -204,3 +219,26 @@ fn struct_with_flattened_field_as_element() {
         "Flattened field should be serialized as content only"
     );
 }
+
+#[test]
+fn struct_with_attributes_by_default_as_element() {
+    let content = StructWithAttributesByDefault {
+        str_attr: "arbitrary text",
+        string_attr: String::from("other text"),
+        child: ChildType {
+            _grandchild: "this text shouldn't show up",
+        },
+        flattened_child: ChildType {
+            _grandchild: "this text shouldn't show up",
+        },
+    };
+
+    let expected = r#"<parent StrAttr="arbitrary text" other_ns:StringAttr="other text"><Child>bare text child node</Child>bare text child node</parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Unmarked fields should be serialized as attributes, while marked and flattened fields should be serialized as content"
+    );
+}
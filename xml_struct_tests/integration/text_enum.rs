@@ -2,8 +2,10 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use xml_struct::XmlSerialize;
-use xml_struct_tests::{serialize_value_as_element, serialize_value_children};
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{
+    deserialize_value_from_str, serialize_value_as_element, serialize_value_children,
+};
 
 #[derive(XmlSerialize)]
 #[xml_struct(text)]
@@ -13,6 +15,24 @@ enum TextEnum {
     Gamma,
 }
 
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(text)]
+enum PaddedTextEnum {
+    #[xml_struct(rename = " Padded ")]
+    Padded,
+
+    #[xml_struct(rename = "Padded")]
+    Unpadded,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct StructWithPaddedTextEnum {
+    #[xml_struct(attribute)]
+    attr: PaddedTextEnum,
+
+    child: PaddedTextEnum,
+}
+
 #[derive(XmlSerialize)]
 struct StructWithTextEnumFields {
     child_field: String,
@@ -92,3 +112,41 @@ fn struct_with_text_enum_fields_as_element() {
         "Text enum attributes should be serialized as text values"
     )
 }
+
+#[test]
+fn text_enum_with_whitespace_round_trip() {
+    let content = StructWithPaddedTextEnum {
+        attr: PaddedTextEnum::Padded,
+        child: PaddedTextEnum::Padded,
+    };
+
+    let expected = r#"<Struct Attr=" Padded "><Child> Padded </Child></Struct>"#;
+
+    let actual = serialize_value_as_element(&content, "Struct").expect("Failed to write struct");
+
+    assert_eq!(actual, expected, "Whitespace in values should be preserved");
+
+    let actual: StructWithPaddedTextEnum =
+        deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual, content,
+        "Values with surrounding whitespace should be matched exactly"
+    );
+
+    let actual: StructWithPaddedTextEnum = deserialize_value_from_str(
+        r#"<Struct Attr="Padded"><Child>
+            Padded
+        </Child></Struct>"#,
+    )
+    .expect("Failed to read struct");
+
+    assert_eq!(
+        actual,
+        StructWithPaddedTextEnum {
+            attr: PaddedTextEnum::Unpadded,
+            child: PaddedTextEnum::Unpadded,
+        },
+        "Values should be matched without surrounding whitespace otherwise"
+    );
+}
@@ -0,0 +1,104 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::{XmlDeserialize, XmlSerialize};
+use xml_struct_tests::{deserialize_value_from_str, serialize_value_as_element};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Body {
+    #[xml_struct(attribute)]
+    body_type: BodyType,
+
+    #[xml_struct(attribute)]
+    is_truncated: Option<bool>,
+
+    #[xml_struct(text)]
+    content: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[xml_struct(text)]
+enum BodyType {
+    #[xml_struct(rename = "HTML")]
+    Html,
+    Text,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+struct Message {
+    subject: String,
+    body: Body,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+enum Annotation {
+    Note {
+        #[xml_struct(attribute)]
+        author: String,
+
+        #[xml_struct(text)]
+        content: String,
+    },
+}
+
+#[test]
+fn struct_with_text_field() {
+    let content = Message {
+        subject: String::from("Greetings"),
+        body: Body {
+            body_type: BodyType::Html,
+            is_truncated: None,
+            content: String::from("<p>some text</p>"),
+        },
+    };
+
+    let expected = r#"<parent><Subject>Greetings</Subject><Body BodyType="HTML">&lt;p&gt;some text&lt;/p&gt;</Body></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write struct");
+
+    assert_eq!(
+        actual, expected,
+        "Text field should be serialized as the text content of the element"
+    );
+
+    let actual: Message = deserialize_value_from_str(&actual).expect("Failed to read struct");
+
+    assert_eq!(
+        actual.body,
+        Body {
+            body_type: BodyType::Html,
+            is_truncated: None,
+            content: String::from("<p>some text</p>"),
+        },
+        "Text field should be deserialized from the text content of the element"
+    );
+}
+
+#[test]
+fn enum_with_text_field() {
+    let content = Annotation::Note {
+        author: String::from("Someone"),
+        content: String::from("Remember this"),
+    };
+
+    let expected = r#"<parent><Note Author="Someone">Remember this</Note></parent>"#;
+
+    let actual = serialize_value_as_element(content, "parent").expect("Failed to write enum");
+
+    assert_eq!(
+        actual, expected,
+        "Text field should be serialized as the text content of the variant element"
+    );
+
+    let actual: Annotation = deserialize_value_from_str(&actual).expect("Failed to read enum");
+
+    assert_eq!(
+        actual,
+        Annotation::Note {
+            author: String::from("Someone"),
+            content: String::from("Remember this"),
+        },
+        "Text field should be deserialized from the text content of the variant element"
+    );
+}
@@ -21,7 +21,7 @@ impl ChildType {
     #[allow(dead_code)]
     fn serialize_child_nodes<W>(
         &self,
-        _writer: &mut quick_xml::Writer<W>,
+        _writer: &mut xml_struct::XmlWriter<W>,
     ) -> Result<(), xml_struct::Error>
     where
         W: std::io::Write,
@@ -36,7 +36,7 @@ impl ChildType {
 impl XmlSerialize for ChildType {
     fn serialize_child_nodes<W>(
         &self,
-        writer: &mut quick_xml::Writer<W>,
+        writer: &mut xml_struct::XmlWriter<W>,
     ) -> Result<(), xml_struct::Error>
     where
         W: std::io::Write,
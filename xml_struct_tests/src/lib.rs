@@ -2,16 +2,16 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use quick_xml::Writer;
+use quick_xml::Reader;
 use thiserror::Error;
-use xml_struct::XmlSerialize;
+use xml_struct::{XmlDeserialize, XmlSerialize, XmlWriter};
 
 pub fn serialize_value_as_element<T>(value: T, root_name: &str) -> Result<String, TestError>
 where
     T: XmlSerialize,
 {
     let buf = Vec::default();
-    let mut writer = Writer::new(buf);
+    let mut writer = XmlWriter::new(buf);
 
     value.serialize_as_element(&mut writer, root_name)?;
 
@@ -25,7 +25,7 @@ where
     T: XmlSerialize,
 {
     let buf = Vec::default();
-    let mut writer = Writer::new(buf);
+    let mut writer = XmlWriter::new(buf);
 
     value.serialize_child_nodes(&mut writer)?;
 
@@ -34,6 +34,17 @@ where
     Ok(out)
 }
 
+pub fn deserialize_value_from_str<T>(xml: &str) -> Result<T, TestError>
+where
+    T: XmlDeserialize,
+{
+    let mut reader = Reader::from_str(xml);
+
+    let value = T::deserialize_from_reader(&mut reader)?;
+
+    Ok(value)
+}
+
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum TestError {
@@ -17,6 +17,37 @@ fn type_properties() {
     t.compile_fail(base_path.join("text_enum_with_non_unit_variants.rs"));
     t.compile_fail(base_path.join("text_enum_with_namespaces.rs"));
     t.compile_fail(base_path.join("invalid_attributes.rs"));
+    t.compile_fail(base_path.join("invalid_rename_all.rs"));
+    t.compile_fail(base_path.join("invalid_default_field_repr.rs"));
+    t.compile_fail(base_path.join("invalid_namespaces.rs"));
+    t.compile_fail(base_path.join("duplicate_names.rs"));
+}
+
+#[test]
+fn field_properties() {
+    let base_path = test_case_base_path().join("field_properties");
+
+    let t = trybuild::TestCases::new();
+    t.compile_fail(base_path.join("invalid_cdata.rs"));
+    t.compile_fail(base_path.join("invalid_item.rs"));
+    t.compile_fail(base_path.join("invalid_list.rs"));
+    t.compile_fail(base_path.join("invalid_map.rs"));
+    t.compile_fail(base_path.join("invalid_namespaces.rs"));
+    t.compile_fail(base_path.join("invalid_rename.rs"));
+    t.compile_fail(base_path.join("invalid_repeated.rs"));
+    t.compile_fail(base_path.join("invalid_skip.rs"));
+    t.compile_fail(base_path.join("invalid_text.rs"));
+    t.compile_fail(base_path.join("invalid_with.rs"));
+}
+
+#[test]
+fn variant_properties() {
+    let base_path = test_case_base_path().join("variant_properties");
+
+    let t = trybuild::TestCases::new();
+    t.compile_fail(base_path.join("invalid_rename.rs"));
+    t.compile_fail(base_path.join("invalid_namespaces.rs"));
+    t.compile_fail(base_path.join("duplicate_names.rs"));
 }
 
 fn test_case_base_path() -> PathBuf {
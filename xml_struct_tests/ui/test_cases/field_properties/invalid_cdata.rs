@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct AttributeAsCdata {
+    #[xml_struct(attribute, cdata)]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct CdataWithFunction {
+    #[xml_struct(cdata, serialize_with = "write_field")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct AttributeItems {
+    #[xml_struct(attribute, item = "Item")]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct NonLiteralItemName {
+    #[xml_struct(item = Item)]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct ItemsWithFunction {
+    #[xml_struct(item = "Item", with = "module")]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct ItemsAsCdata {
+    #[xml_struct(item = "Item", cdata)]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct EmptyItemName {
+    #[xml_struct(item = "")]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct InvalidItemName {
+    #[xml_struct(item = "bad item")]
+    field: Vec<String>,
+}
+
+fn main() {}
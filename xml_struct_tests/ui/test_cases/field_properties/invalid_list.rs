@@ -0,0 +1,31 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct ListAsElement {
+    #[xml_struct(list)]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct EmptySeparator {
+    #[xml_struct(attribute, list = "")]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct MultipleSeparators {
+    #[xml_struct(attribute, list, list = ",")]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct ListWithFunction {
+    #[xml_struct(attribute, list, with = "module")]
+    field: Vec<String>,
+}
+
+fn main() {}
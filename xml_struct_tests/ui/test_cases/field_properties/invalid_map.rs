@@ -0,0 +1,57 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::BTreeMap;
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct UnrecognizedMap {
+    #[xml_struct(map = "list")]
+    field: BTreeMap<String, String>,
+}
+
+#[derive(XmlSerialize)]
+struct KeyWithoutEntries {
+    #[xml_struct(key = "Name")]
+    field: BTreeMap<String, String>,
+}
+
+#[derive(XmlSerialize)]
+struct ItemWithAttributes {
+    #[xml_struct(map = "attributes", item = "Entry")]
+    field: BTreeMap<String, String>,
+}
+
+#[derive(XmlSerialize)]
+struct AttributesAsElement {
+    #[xml_struct(map = "attributes", element)]
+    field: BTreeMap<String, String>,
+}
+
+#[derive(XmlSerialize)]
+struct EntriesAsAttribute {
+    #[xml_struct(map = "entries", attribute)]
+    field: BTreeMap<String, String>,
+}
+
+#[derive(XmlSerialize)]
+struct RepeatedMap {
+    #[xml_struct(map = "entries", repeated)]
+    field: BTreeMap<String, String>,
+}
+
+#[derive(XmlSerialize)]
+struct EmptyKeyName {
+    #[xml_struct(map = "entries", key = "")]
+    field: BTreeMap<String, String>,
+}
+
+#[derive(XmlSerialize)]
+struct InvalidKeyName {
+    #[xml_struct(map = "entries", key = "bad key")]
+    field: BTreeMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,54 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+const TYPES_NS: &str = "http://types.example/";
+
+#[derive(XmlSerialize)]
+struct NamespaceWithPrefix {
+    #[xml_struct(ns = TYPES_NS, ns_prefix = "t")]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct NamespaceOnTextField {
+    #[xml_struct(text, ns = TYPES_NS)]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct NamespaceOnFlattenedField {
+    #[xml_struct(flatten, ns = TYPES_NS)]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct NamespaceOnUnnamedField(#[xml_struct(ns = TYPES_NS)] String);
+
+#[derive(XmlSerialize)]
+struct EmptyNamespace {
+    #[xml_struct(ns = "")]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct ReservedNamespace {
+    #[xml_struct(attribute, ns = "http://www.w3.org/2000/xmlns/")]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct MultipleNamespaces {
+    #[xml_struct(ns = TYPES_NS, ns = "http://other.example/")]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = TYPES_NS, ns = "http://other.example/")]
+struct MultipleTypeNamespaces {
+    field: String,
+}
+
+fn main() {}
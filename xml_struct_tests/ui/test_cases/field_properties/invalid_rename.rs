@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct RenamedUnnamedField(#[xml_struct(rename = "Foo")] String);
+
+#[derive(XmlSerialize)]
+struct MultipleNames {
+    #[xml_struct(rename = "Foo", rename = "Bar")]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct NonLiteralName {
+    #[xml_struct(rename = Foo)]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct EmptyName {
+    #[xml_struct(rename = "")]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct InvalidName {
+    #[xml_struct(rename = "bad name")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct RepeatedAttribute {
+    #[xml_struct(attribute, repeated)]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct RepeatedFlattened {
+    #[xml_struct(flatten, repeated)]
+    field: Vec<String>,
+}
+
+#[derive(XmlSerialize)]
+struct RepeatedUnnamed(#[xml_struct(repeated)] Vec<String>);
+
+#[derive(XmlSerialize)]
+struct RepeatedItems {
+    #[xml_struct(repeated, item = "Item")]
+    field: Vec<String>,
+}
+
+fn main() {}
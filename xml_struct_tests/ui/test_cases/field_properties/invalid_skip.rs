@@ -0,0 +1,25 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct NonLiteralCondition {
+    #[xml_struct(skip_serializing_if = Option::is_none)]
+    field: Option<String>,
+}
+
+#[derive(XmlSerialize)]
+struct InvalidPath {
+    #[xml_struct(skip_serializing_if = "not a path")]
+    field: Option<String>,
+}
+
+#[derive(XmlSerialize)]
+struct MultipleConditions {
+    #[xml_struct(skip_serializing_if = "Option::is_none", skip_serializing_if = "Option::is_some")]
+    field: Option<String>,
+}
+
+fn main() {}
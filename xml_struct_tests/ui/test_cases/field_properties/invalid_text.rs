@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct TextWithElement {
+    #[xml_struct(text)]
+    text: String,
+
+    element: String,
+}
+
+#[derive(XmlSerialize)]
+struct MultipleTextFields {
+    #[xml_struct(text)]
+    text: String,
+
+    #[xml_struct(text)]
+    more_text: String,
+}
+
+#[derive(XmlSerialize)]
+enum VariantTextWithFlattened {
+    Variant {
+        #[xml_struct(flatten)]
+        flattened: String,
+
+        #[xml_struct(text)]
+        text: String,
+    },
+}
+
+#[derive(XmlSerialize)]
+struct ConflictingRepresentations {
+    #[xml_struct(attribute, text)]
+    attr_text: String,
+
+    #[xml_struct(text, flatten)]
+    flattened_text: String,
+}
+
+fn main() {}
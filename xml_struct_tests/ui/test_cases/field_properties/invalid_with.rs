@@ -0,0 +1,25 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+struct NonLiteralFunction {
+    #[xml_struct(serialize_with = write_field)]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct MultipleFunctions {
+    #[xml_struct(serialize_with = "write_field", serialize_with = "write_field")]
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+struct FunctionAndModule {
+    #[xml_struct(serialize_with = "write_field", with = "module")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,58 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+struct DuplicateAttributes {
+    item_id: String,
+
+    #[xml_struct(rename = "ItemId")]
+    other_item_id: String,
+
+    #[xml_struct(ns_prefix = "foo", rename = "ItemId")]
+    prefixed_item_id: String,
+
+    #[xml_struct(ns_prefix = "foo", rename = "ItemId")]
+    other_prefixed_item_id: String,
+
+    #[xml_struct(skip, rename = "ItemId")]
+    skipped_item_id: String,
+}
+
+#[derive(XmlSerialize)]
+enum DuplicateVariantAttributes {
+    Variant {
+        #[xml_struct(attribute)]
+        id: String,
+
+        #[xml_struct(attribute, rename = "Id")]
+        other_id: String,
+    },
+}
+
+#[derive(XmlSerialize)]
+struct DuplicateElements {
+    #[xml_struct(rename = "Item")]
+    first: String,
+
+    #[xml_struct(rename = "Item")]
+    second: String,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(deny_duplicate_elements)]
+struct DeniedDuplicateElements {
+    #[xml_struct(rename = "Item")]
+    first: String,
+
+    #[xml_struct(rename = "Item", attribute)]
+    attribute: String,
+
+    #[xml_struct(rename = "Item")]
+    second: String,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[xml_struct(fields = "text")]
+struct UnrecognizedRepresentation {
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(fields = "attribute", fields = "element")]
+struct MultipleRepresentations {
+    field: String,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(fields = "attribute")]
+struct FlattenedAttribute {
+    #[xml_struct(attribute, flatten)]
+    field: String,
+}
+
+fn main() {}
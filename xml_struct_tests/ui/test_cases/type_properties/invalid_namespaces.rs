@@ -0,0 +1,53 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+const FOO_NAME: &str = "http://foo.example/";
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("foo", "http://foo.example/"), ns = ("foo", "http://bar.example/"))]
+struct DuplicatePrefix;
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("foo:bar", "http://foo.example/"), ns = ("1foo", "http://foo.example/"), ns = ("", "http://foo.example/"))]
+struct InvalidPrefix;
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("xmlns", "http://www.w3.org/2000/xmlns/"))]
+struct XmlnsPrefix;
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("xml", "http://foo.example/"), ns = ("foo", "http://www.w3.org/XML/1998/namespace"))]
+struct MisboundXmlPrefix;
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("xml", FOO_NAME))]
+struct XmlPrefixByVariable;
+
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("foo", ""), ns = ("bar", "http://www.w3.org/2000/xmlns/"))]
+struct InvalidNamespaceName;
+
+#[derive(XmlSerialize)]
+#[xml_struct(default_ns = "")]
+struct EmptyDefaultNamespace;
+
+#[derive(XmlSerialize)]
+#[xml_struct(default_ns = "http://www.w3.org/XML/1998/namespace")]
+struct ReservedDefaultNamespace;
+
+#[derive(XmlSerialize)]
+#[xml_struct(variant_ns_prefix = "foo bar")]
+enum InvalidVariantPrefix {
+    Foo,
+}
+
+#[derive(XmlSerialize)]
+struct InvalidFieldPrefix {
+    #[xml_struct(ns_prefix = "xmlns")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[xml_struct(rename_all = "SCREAMING_SNAKE_CASE")]
+struct UnrecognizedCaseConvention;
+
+const CASE: &str = "camelCase";
+
+#[derive(XmlSerialize)]
+#[xml_struct(rename_all = CASE)]
+struct NonLiteralCaseConvention;
+
+#[derive(XmlSerialize)]
+#[xml_struct(rename_all = "camelCase", rename_all = "snake_case")]
+struct MultipleCaseConventions;
+
+fn main() {}
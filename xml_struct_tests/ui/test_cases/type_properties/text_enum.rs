@@ -15,7 +15,7 @@ enum UnitVariants {
 
 fn main() -> Result<(), xml_struct::Error> {
     let bytes: Vec<u8> = Vec::new();
-    let mut writer = quick_xml::writer::Writer::new(bytes);
+    let mut writer = xml_struct::XmlWriter::new(bytes);
 
     let content = UnitVariants::Bar;
     content.serialize_as_element(&mut writer, "foo")?;
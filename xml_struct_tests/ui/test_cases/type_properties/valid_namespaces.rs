@@ -27,9 +27,16 @@ struct DefaultNamespace;
 #[xml_struct(default_ns = "http://default.example/", ns = ("foo", "http://foo.example/"), ns = ("bar", BAR_NAME))]
 struct DefaultNamespaceWithOthers;
 
+#[derive(XmlSerialize)]
+#[xml_struct(ns = ("xml", "http://www.w3.org/XML/1998/namespace"))]
+struct XmlNamespace {
+    #[xml_struct(attribute, ns_prefix = "xml")]
+    lang: String,
+}
+
 fn main() -> Result<(), xml_struct::Error> {
     let bytes: Vec<u8> = Vec::new();
-    let mut writer = quick_xml::writer::Writer::new(bytes);
+    let mut writer = xml_struct::XmlWriter::new(bytes);
 
     let content = SingleNamespace;
     content.serialize_as_element(&mut writer, "foo")?;
@@ -46,5 +53,10 @@ fn main() -> Result<(), xml_struct::Error> {
     let content = DefaultNamespaceWithOthers;
     content.serialize_as_element(&mut writer, "foo")?;
 
+    let content = XmlNamespace {
+        lang: String::from("en"),
+    };
+    content.serialize_as_element(&mut writer, "foo")?;
+
     Ok(())
 }
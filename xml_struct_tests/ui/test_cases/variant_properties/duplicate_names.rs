@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+enum DuplicateVariants {
+    ItemId,
+
+    #[xml_struct(rename = "ItemId")]
+    Other,
+
+    #[xml_struct(ns_prefix = "foo", rename = "ItemId")]
+    Prefixed,
+
+    #[xml_struct(ns_prefix = "foo", rename = "ItemId")]
+    OtherPrefixed,
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(rename_all = "lowercase")]
+enum DuplicateRenamedVariants {
+    Item(String),
+    ITEM { id: String },
+}
+
+#[derive(XmlSerialize)]
+#[xml_struct(text)]
+enum DuplicateTextValues {
+    #[xml_struct(rename = "1.0")]
+    First,
+
+    #[xml_struct(rename = "1.0")]
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[xml_struct(text)]
+enum TextVariantNamespace {
+    #[xml_struct(ns_prefix = "t")]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum PrefixWithNamespaceName {
+    #[xml_struct(ns_prefix = "t", ns = "http://types.example/")]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum MultiplePrefixes {
+    #[xml_struct(ns_prefix = "t", ns_prefix = "m")]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum MultipleDefaultNamespaces {
+    #[xml_struct(default_ns = "http://types.example/", default_ns = "http://messages.example/")]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum DuplicatePrefix {
+    #[xml_struct(ns = ("t", "http://types.example/"), ns = ("t", "http://other.example/"))]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum InvalidPrefix {
+    #[xml_struct(ns_prefix = "t:m")]
+    Variant,
+}
+
+fn main() {}
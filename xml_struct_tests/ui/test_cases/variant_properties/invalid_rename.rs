@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use xml_struct::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[xml_struct(text)]
+enum MultipleNames {
+    #[xml_struct(rename = "Foo", rename = "Bar")]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum NonLiteralName {
+    #[xml_struct(rename = Foo)]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum UnrecognizedAttribute {
+    #[xml_struct(flatten)]
+    Variant(String),
+}
+
+#[derive(XmlSerialize)]
+enum EmptyName {
+    #[xml_struct(rename = "")]
+    Variant,
+}
+
+#[derive(XmlSerialize)]
+enum InvalidName {
+    #[xml_struct(rename = "bad name")]
+    Variant(String),
+}
+
+// Text variants are serialized as values, so aren't subject to name rules.
+#[derive(XmlSerialize)]
+#[xml_struct(text)]
+enum TextVariantWithSpaces {
+    #[xml_struct(rename = "not a name")]
+    Variant,
+}
+
+fn main() {}